@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+
+use crate::error::AppError;
+use crate::output::report::print_history;
+use crate::state::journal::Journal;
+use crate::targets::category::Category;
+
+pub struct HistoryOptions {
+    pub categories: Vec<Category>,
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound.
+    pub until: Option<DateTime<Utc>>,
+}
+
+pub fn execute(options: HistoryOptions) -> Result<(), AppError> {
+    let records: Vec<_> = Journal::open()?
+        .records()?
+        .into_iter()
+        .filter(|record| {
+            options.categories.is_empty() || options.categories.contains(&record.category)
+        })
+        .filter(|record| options.since.is_none_or(|since| record.timestamp >= since))
+        .filter(|record| options.until.is_none_or(|until| record.timestamp < until))
+        .collect();
+
+    print_history(&records);
+    Ok(())
+}
@@ -1,2 +1,5 @@
+pub mod history;
+pub mod plan;
+pub mod quarantine;
 pub mod run;
 pub mod scan;
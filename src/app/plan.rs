@@ -0,0 +1,237 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::fs::size::path_size;
+use crate::output::bytes::format_bytes;
+use crate::targets::catalog;
+use crate::targets::category::Category;
+use crate::targets::item::{CleanupItem, ItemKind};
+use crate::targets::report::ScanReport;
+use crate::targets::target::ScanScope;
+
+const PLAN_VERSION: u32 = 1;
+
+/// Size changes below this many bytes are never treated as drift.
+const SIZE_DRIFT_FLOOR: u64 = 1024 * 1024;
+
+/// A scan report saved by `prf scan --save-plan` for later execution with `prf run --plan`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Plan {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub categories: Vec<Category>,
+    /// Scan roots, so revalidation sees the same project pins the scan did.
+    #[serde(default)]
+    pub roots: Vec<PathBuf>,
+    pub report: ScanReport,
+}
+
+impl Plan {
+    pub fn new(categories: &[Category], roots: &[PathBuf], report: ScanReport) -> Self {
+        Self {
+            version: PLAN_VERSION,
+            created_at: Utc::now(),
+            categories: categories.to_vec(),
+            roots: roots.to_vec(),
+            report,
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AppError> {
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AppError> {
+        let plan: Plan = serde_json::from_slice(&fs::read(path)?)?;
+        if plan.version != PLAN_VERSION {
+            return Err(AppError::InvalidPlan(format!(
+                "{} has version {}, expected {PLAN_VERSION}",
+                path.display(),
+                plan.version
+            )));
+        }
+        Ok(plan)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftReason {
+    Missing,
+    KindChanged,
+    RuleMismatch,
+    SizeChanged { planned: u64, current: u64 },
+}
+
+impl fmt::Display for DriftReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriftReason::Missing => write!(f, "no longer exists"),
+            DriftReason::KindChanged => write!(f, "changed between file and directory"),
+            DriftReason::RuleMismatch => write!(f, "no longer matches its category rule"),
+            DriftReason::SizeChanged { planned, current } => write!(
+                f,
+                "size changed from {} to {}",
+                format_bytes(*planned),
+                format_bytes(*current)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DriftedItem {
+    pub item: CleanupItem,
+    pub reason: DriftReason,
+}
+
+/// Re-checks every planned item against the filesystem. Returns the items that are still safe
+/// to act on (with freshly measured sizes) and the ones that drifted since the plan was saved.
+pub fn revalidate(
+    plan: &Plan,
+    categories: &[Category],
+    verbose: bool,
+) -> Result<(ScanReport, Vec<DriftedItem>), AppError> {
+    let scope = ScanScope::new(plan.roots.clone(), false, verbose);
+    let targets = catalog::build_targets(categories, &scope);
+    let mut report = ScanReport::new();
+    let mut drifted = Vec::new();
+
+    for category in categories {
+        let Some(category_report) = plan.report.report_for(*category) else {
+            continue;
+        };
+        let category_targets: Vec<_> =
+            targets.iter().filter(|target| target.category() == *category).collect();
+
+        for item in &category_report.items {
+            // Docker is re-measured by `docker system prune` itself; only the rule applies.
+            if *category == Category::Docker {
+                if category_targets.iter().any(|target| target.owns(&item.path)) {
+                    report.add_items(*category, vec![item.clone()]);
+                }
+                continue;
+            }
+
+            match check_item(item, |path| category_targets.iter().any(|t| t.owns(path)), verbose) {
+                Ok(current_size) => {
+                    let mut refreshed = item.clone();
+                    refreshed.size = current_size;
+                    report.add_items(*category, vec![refreshed]);
+                }
+                Err(reason) => drifted.push(DriftedItem { item: item.clone(), reason }),
+            }
+        }
+    }
+
+    Ok((report, drifted))
+}
+
+fn check_item(
+    item: &CleanupItem,
+    owns: impl Fn(&Path) -> bool,
+    verbose: bool,
+) -> Result<u64, DriftReason> {
+    let Ok(metadata) = fs::symlink_metadata(&item.path) else {
+        return Err(DriftReason::Missing);
+    };
+
+    let kind = if metadata.is_dir() { ItemKind::Directory } else { ItemKind::File };
+    if kind != item.kind {
+        return Err(DriftReason::KindChanged);
+    }
+
+    if !owns(&item.path) {
+        return Err(DriftReason::RuleMismatch);
+    }
+
+    let current = match kind {
+        ItemKind::Directory => path_size(&item.path, verbose).map_err(|_| DriftReason::Missing)?,
+        ItemKind::File => metadata.len(),
+    };
+    if size_drifted(item.size, current) {
+        return Err(DriftReason::SizeChanged { planned: item.size, current });
+    }
+
+    Ok(current)
+}
+
+/// A size has drifted when it more than doubled or halved and moved by over `SIZE_DRIFT_FLOOR`.
+fn size_drifted(planned: u64, current: u64) -> bool {
+    let (low, high) = if planned <= current { (planned, current) } else { (current, planned) };
+    high - low > SIZE_DRIFT_FLOOR && high > low.saturating_mul(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn size_drifted_ignores_small_and_proportional_changes() {
+        assert!(!size_drifted(10, 5_000));
+        assert!(!size_drifted(100 * SIZE_DRIFT_FLOOR, 150 * SIZE_DRIFT_FLOOR));
+        assert!(size_drifted(2 * SIZE_DRIFT_FLOOR, 10 * SIZE_DRIFT_FLOOR));
+        assert!(size_drifted(10 * SIZE_DRIFT_FLOOR, SIZE_DRIFT_FLOOR));
+    }
+
+    #[test]
+    fn revalidate_skips_missing_mismatched_and_retyped_items() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let kept = temp.child("app/node_modules");
+        kept.child("index.js").write_str("console.log('cache');").expect("file exists");
+        let renamed = temp.child("lib/vendor");
+        renamed.create_dir_all().expect("directory exists");
+        let retyped = temp.child("web/.next");
+        retyped.write_str("now a file").expect("file exists");
+
+        let mut report = ScanReport::new();
+        report.add_items(
+            Category::Nodejs,
+            vec![
+                CleanupItem::directory(Category::Nodejs, kept.path().to_path_buf(), 21),
+                CleanupItem::directory(Category::Nodejs, temp.path().join("gone/node_modules"), 1),
+                CleanupItem::directory(Category::Nodejs, renamed.path().to_path_buf(), 0),
+                CleanupItem::directory(Category::Nodejs, retyped.path().to_path_buf(), 0),
+            ],
+        );
+        let plan = Plan::new(&[Category::Nodejs], &[], report);
+
+        let (valid, drifted) =
+            revalidate(&plan, &[Category::Nodejs], false).expect("revalidation succeeds");
+
+        let valid_items = &valid.report_for(Category::Nodejs).expect("nodejs remains").items;
+        assert_eq!(valid_items.len(), 1);
+        assert_eq!(valid_items[0].path, kept.path());
+
+        let reasons: Vec<_> = drifted.iter().map(|drift| drift.reason.clone()).collect();
+        assert_eq!(
+            reasons,
+            vec![DriftReason::Missing, DriftReason::RuleMismatch, DriftReason::KindChanged]
+        );
+    }
+
+    #[test]
+    fn plan_round_trips_through_json() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let mut report = ScanReport::new();
+        report.add_items(
+            Category::Rust,
+            vec![CleanupItem::directory(Category::Rust, temp.path().join("target"), 7)],
+        );
+
+        let path = temp.path().join("plan.json");
+        Plan::new(&[Category::Rust], &[], report).save(&path).expect("plan is saved");
+        let loaded = Plan::load(&path).expect("plan is loaded");
+
+        assert_eq!(loaded.categories, vec![Category::Rust]);
+        assert_eq!(loaded.report.total_size(), 7);
+    }
+}
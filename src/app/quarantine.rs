@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use chrono::Utc;
+
+use crate::error::AppError;
+use crate::output::bytes::format_bytes;
+use crate::output::prompt::confirm_deletion;
+use crate::output::report::{display_path, print_quarantine_entries};
+use crate::state::journal::{self, JournalAction, JournalRecord};
+use crate::state::quarantine::Quarantine;
+
+pub struct RestoreOptions {
+    pub target: Option<String>,
+    pub verbose: bool,
+}
+
+pub struct PurgeOptions {
+    pub older_than: Option<Duration>,
+    pub assume_yes: bool,
+    pub verbose: bool,
+}
+
+pub fn restore(options: RestoreOptions) -> Result<(), AppError> {
+    let quarantine = Quarantine::open()?;
+
+    let Some(target) = options.target else {
+        print_quarantine_entries(&quarantine.entries()?);
+        return Ok(());
+    };
+
+    // The same path may have been quarantined more than once; the newest copy wins.
+    let matches = quarantine.find(&target)?;
+    let Some(entry) = matches.last() else {
+        return Err(AppError::QuarantineEntryNotFound(target));
+    };
+
+    quarantine.restore(entry, options.verbose)?;
+    println!("Restored {} ({}).", display_path(&entry.original_path), entry.id);
+    Ok(())
+}
+
+pub fn purge(options: PurgeOptions) -> Result<(), AppError> {
+    let quarantine = Quarantine::open()?;
+    let now = Utc::now();
+
+    let entries: Vec<_> = quarantine
+        .entries()?
+        .into_iter()
+        .filter(|entry| {
+            options.older_than.is_none_or(|min_age| {
+                (now - entry.quarantined_at).to_std().is_ok_and(|age| age >= min_age)
+            })
+        })
+        .collect();
+
+    if entries.is_empty() {
+        println!("Nothing to purge.");
+        return Ok(());
+    }
+
+    print_quarantine_entries(&entries);
+    let total: u64 = entries.iter().map(|entry| entry.size).sum();
+    if !options.assume_yes && !confirm_deletion(total)? {
+        println!("Aborted. No files were deleted.");
+        return Ok(());
+    }
+
+    let mut records = Vec::with_capacity(entries.len());
+    let mut result = Ok(());
+    for entry in &entries {
+        let outcome = quarantine.purge(entry, options.verbose);
+        let item = entry.to_item();
+        records.push(JournalRecord::new(JournalAction::Purge, &item, outcome.as_ref().copied()));
+        if outcome.is_err() {
+            result = outcome;
+            break;
+        }
+    }
+    journal::record(&records);
+    result?;
+
+    println!("Purged {} across {} item(s).", format_bytes(total), entries.len());
+    Ok(())
+}
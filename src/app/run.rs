@@ -1,54 +1,83 @@
 use std::io;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use indicatif::{MultiProgress, ProgressBar};
 use rayon::prelude::*;
 
 use crate::error::AppError;
-use crate::fs::remove::remove_item;
+use crate::fs::exclude::ExcludeRules;
+use crate::fs::remove::{RemovalMode, remove_item};
+use crate::fs::trash::move_to_trash;
 use crate::output::bytes::format_bytes;
 use crate::output::progress::deletion_progress_style;
-use crate::output::prompt::{confirm_deletion, prompt_for_categories};
-use crate::output::report::print_deletion_plan;
+use crate::output::prompt::{
+    checklist_available, confirm_deletion, prompt_for_categories, prompt_for_items,
+};
+use crate::output::report::{print_deletion_plan, print_plan_drift};
+use crate::state::journal::{self, JournalAction, JournalRecord};
+use crate::state::quarantine::Quarantine;
 use crate::targets::category::Category;
 use crate::targets::docker;
-use crate::targets::item::CleanupItem;
+use crate::targets::item::{AgeFilter, CleanupItem};
 use crate::targets::report::ScanReport;
+use crate::targets::runtimes;
+use crate::targets::rustup;
 use crate::targets::target::ScanScope;
 
+use super::plan::{Plan, revalidate};
 use super::scan::scan_categories;
 
 pub struct RunOptions {
     pub categories: Vec<Category>,
     pub interactive: bool,
     pub roots: Vec<PathBuf>,
+    pub max_depth: usize,
+    pub excludes: Vec<String>,
+    pub age: AgeFilter,
     pub verbose: bool,
     pub assume_yes: bool,
     pub current: bool,
+    pub mode: RemovalMode,
+    pub plan: Option<PathBuf>,
 }
 
 pub fn execute(options: RunOptions) -> Result<(), AppError> {
     let debug_logging = std::env::var_os("PRF_DEBUG").is_some();
 
-    let scope = ScanScope::new(options.roots, options.current, options.verbose);
     let progress = Arc::new(MultiProgress::new());
-    let report = scan_categories(&options.categories, &scope, &progress)?;
+    let (report, categories) = match &options.plan {
+        Some(plan_path) => load_plan(plan_path, &options.categories, options.verbose)?,
+        None => {
+            let scope = ScanScope::new(options.roots.clone(), options.current, options.verbose)
+                .with_max_depth(options.max_depth)
+                .with_excludes(ExcludeRules::new(&options.excludes)?)
+                .with_age_filter(options.age);
+            (scan_categories(&options.categories, &scope, &progress)?, options.categories.clone())
+        }
+    };
 
     if debug_logging {
         eprintln!("[prf::run] finished scan phase");
     }
 
-    let docker_requested_initially =
-        options.categories.contains(&Category::Docker) && !options.current;
+    // Docker's reclaimable space has no age, so an age filter leaves it untouched.
+    let docker_allowed = !options.current && !options.age.is_active();
+    let docker_requested_initially = categories.contains(&Category::Docker) && docker_allowed;
     if report.total_size() == 0 && !docker_requested_initially {
         println!("Nothing to delete. All selected categories are already clean.");
         return Ok(());
     }
 
-    let selected_categories = if options.interactive {
-        match prompt_for_categories(&report, &options.categories) {
-            Ok(categories) => categories,
+    let (selected_categories, subset) = if options.interactive {
+        let selection = if checklist_available() {
+            prompt_for_items(&report, &categories)
+        } else {
+            prompt_for_categories(&report, &categories)
+                .map(|selected| (selected.clone(), report.subset(&selected)))
+        };
+        match selection {
+            Ok(selection) => selection,
             Err(AppError::Cancelled) => {
                 println!("Aborted. No files were deleted.");
                 return Ok(());
@@ -56,11 +85,10 @@ pub fn execute(options: RunOptions) -> Result<(), AppError> {
             Err(err) => return Err(err),
         }
     } else {
-        options.categories.clone()
+        (categories.clone(), report.subset(&categories))
     };
 
-    let docker_selected = selected_categories.contains(&Category::Docker) && !options.current;
-    let subset = report.subset(&selected_categories);
+    let docker_selected = selected_categories.contains(&Category::Docker) && docker_allowed;
     if subset.total_size() == 0 && !docker_selected {
         println!("Nothing to delete. All selected categories are already clean.");
         return Ok(());
@@ -89,11 +117,18 @@ pub fn execute(options: RunOptions) -> Result<(), AppError> {
     let fs_result = if filesystem_items.is_empty() {
         Ok(())
     } else {
-        delete_items(&filesystem_items, &progress, options.verbose)
+        let result = delete_items(&filesystem_items, &progress, options.mode, options.verbose);
+        runtimes::refresh_shims(&filesystem_items, options.verbose);
+        result
     };
 
-    let docker_result =
-        if docker_selected { run_docker_cleanup_with_handling(options.verbose) } else { Ok(()) };
+    let docker_result = if docker_selected {
+        let docker_size =
+            subset.report_for(Category::Docker).map(|report| report.total_size()).unwrap_or(0);
+        run_docker_cleanup_with_handling(docker_size, options.verbose)
+    } else {
+        Ok(())
+    };
 
     match (fs_result, docker_result) {
         (Ok(()), Ok(())) => {}
@@ -109,15 +144,41 @@ pub fn execute(options: RunOptions) -> Result<(), AppError> {
         eprintln!("[prf::run] deletion phase complete");
     }
 
+    let verb = match options.mode {
+        RemovalMode::Delete => "delete",
+        RemovalMode::Trash => "move to the trash",
+        RemovalMode::Quarantine => "quarantine",
+    };
     println!(
-        "Attempted to delete {} across {} categor(ies).",
+        "Attempted to {verb} {} across {} categor(ies).",
         format_bytes(subset.total_size()),
         selected_categories.len()
     );
+    if options.mode == RemovalMode::Quarantine {
+        println!("Use `prf restore` to bring items back or `prf purge` to free the space.");
+    }
 
     Ok(())
 }
 
+/// Loads a saved plan and revalidates it, restricted to `requested` when non-empty.
+fn load_plan(
+    path: &std::path::Path,
+    requested: &[Category],
+    verbose: bool,
+) -> Result<(ScanReport, Vec<Category>), AppError> {
+    let plan = Plan::load(path)?;
+    let categories: Vec<Category> = if requested.is_empty() {
+        plan.categories.clone()
+    } else {
+        requested.iter().copied().filter(|category| plan.categories.contains(category)).collect()
+    };
+
+    let (report, drifted) = revalidate(&plan, &categories, verbose)?;
+    print_plan_drift(&drifted);
+    Ok((report, categories))
+}
+
 fn flatten_items_for_categories(report: &ScanReport, categories: &[Category]) -> Vec<CleanupItem> {
     categories
         .iter()
@@ -126,22 +187,65 @@ fn flatten_items_for_categories(report: &ScanReport, categories: &[Category]) ->
         .collect()
 }
 
-fn run_docker_cleanup_with_handling(verbose: bool) -> Result<(), AppError> {
-    match docker::run_cleanup(verbose) {
-        Ok(()) => Ok(()),
-        Err(AppError::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
-            if verbose {
-                eprintln!("Docker CLI not available; skipping Docker cleanup.");
+fn run_docker_cleanup_with_handling(reclaimable: u64, verbose: bool) -> Result<(), AppError> {
+    let result = docker::run_cleanup(verbose);
+    if let Err(AppError::Io(err)) = &result
+        && err.kind() == io::ErrorKind::NotFound
+    {
+        if verbose {
+            eprintln!("Docker CLI not available; skipping Docker cleanup.");
+        }
+        return Ok(());
+    }
+
+    let item = CleanupItem::directory(
+        Category::Docker,
+        PathBuf::from(docker::DOCKER_SCAN_LABEL),
+        reclaimable,
+    );
+    journal::record(&[JournalRecord::new(JournalAction::Prune, &item, result.as_ref().copied())]);
+    result
+}
+
+fn remove_prepared_item(
+    item: &CleanupItem,
+    mode: RemovalMode,
+    quarantine: Option<&Quarantine>,
+    verbose: bool,
+) -> Result<(), AppError> {
+    match mode {
+        RemovalMode::Delete => {
+            if item.category == Category::Rustup
+                && let Some(name) = rustup::toolchain_name(&item.path)
+                && rustup::uninstall(&name, verbose)?
+            {
+                return Ok(());
             }
-            Ok(())
+            remove_item(&item.path, item.kind, verbose)
+        }
+        RemovalMode::Trash => move_to_trash(&item.path, verbose),
+        RemovalMode::Quarantine => {
+            let store = quarantine.ok_or_else(|| io::Error::other("quarantine is not open"))?;
+            store.stash(item, verbose).map(|_| ())
         }
-        Err(err) => Err(err),
     }
 }
 
+/// Resolves `path` for de-duplication. A symlink item (such as a Bazel `bazel-*` link) is the
+/// link itself, so only its parent directory is resolved.
+fn canonical_location(path: &std::path::Path) -> PathBuf {
+    let is_link = std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_symlink());
+    let resolved = match (is_link, path.parent(), path.file_name()) {
+        (true, Some(parent), Some(name)) => std::fs::canonicalize(parent).map(|dir| dir.join(name)),
+        _ => std::fs::canonicalize(path),
+    };
+    resolved.unwrap_or_else(|_| path.to_path_buf())
+}
+
 fn delete_items(
     items: &[CleanupItem],
     progress: &Arc<MultiProgress>,
+    mode: RemovalMode,
     verbose: bool,
 ) -> Result<(), AppError> {
     if items.is_empty() {
@@ -152,7 +256,7 @@ fn delete_items(
     let mut seen_paths: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
 
     for item in items {
-        let canonicalized = std::fs::canonicalize(&item.path).unwrap_or_else(|_| item.path.clone());
+        let canonicalized = canonical_location(&item.path);
         let key = canonicalized.to_string_lossy().into_owned();
 
         if let Some(index) = seen_paths.get(&key).copied() {
@@ -168,19 +272,30 @@ fn delete_items(
             path: canonicalized,
             size: item.size,
             kind: item.kind,
+            modified: item.modified,
+            project: item.project.clone(),
         });
     }
 
     prepared_items.sort_by_key(|item| std::cmp::Reverse(item.path.components().count()));
 
+    let quarantine = if mode == RemovalMode::Quarantine { Some(Quarantine::open()?) } else { None };
+
     let pb = progress.add(ProgressBar::new(prepared_items.len() as u64));
     pb.set_style(deletion_progress_style());
 
-    prepared_items.par_iter().try_for_each(|item| {
-        remove_item(&item.path, item.kind, verbose)?;
+    let records = Mutex::new(Vec::with_capacity(prepared_items.len()));
+    let result = prepared_items.par_iter().try_for_each(|item| {
+        let outcome = remove_prepared_item(item, mode, quarantine.as_ref(), verbose);
+        let record = JournalRecord::new(mode.into(), item, outcome.as_ref().copied());
+        records.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(record);
+        outcome?;
         pb.inc(1);
         Ok::<(), AppError>(())
-    })?;
+    });
+
+    journal::record(&records.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    result?;
 
     pb.finish_and_clear();
     let _ = progress.println(format!(
@@ -195,15 +310,44 @@ fn delete_items(
 mod tests {
     use assert_fs::TempDir;
     use assert_fs::prelude::*;
+    use serial_test::serial;
 
     use crate::targets::category::Category;
     use crate::targets::item::CleanupItem;
 
     use super::*;
 
+    /// Points the journal at a temporary state directory for the lifetime of the guard.
+    struct StateDirGuard {
+        original: Option<std::ffi::OsString>,
+    }
+
+    impl StateDirGuard {
+        fn set(dir: &std::path::Path) -> Self {
+            let original = std::env::var_os("PRF_STATE_DIR");
+            unsafe {
+                std::env::set_var("PRF_STATE_DIR", dir);
+            }
+            Self { original }
+        }
+    }
+
+    impl Drop for StateDirGuard {
+        fn drop(&mut self) {
+            unsafe {
+                match &self.original {
+                    Some(value) => std::env::set_var("PRF_STATE_DIR", value),
+                    None => std::env::remove_var("PRF_STATE_DIR"),
+                }
+            }
+        }
+    }
+
     #[test]
+    #[serial]
     fn delete_items_removes_files_and_directories() {
         let temp = TempDir::new().expect("temp directory is created");
+        let _state = StateDirGuard::set(&temp.path().join("state"));
         let dir = temp.child("node_modules");
         dir.child("lib").create_dir_all().expect("directory exists");
         dir.child("lib/index.js").write_str("console.log('cache');").expect("file exists");
@@ -216,15 +360,22 @@ mod tests {
         ];
 
         let progress = Arc::new(MultiProgress::new());
-        delete_items(&items, &progress, false).expect("deletion succeeds");
+        delete_items(&items, &progress, RemovalMode::Delete, false).expect("deletion succeeds");
 
         dir.assert(predicates::path::missing());
         file.assert(predicates::path::missing());
+
+        let records = crate::state::journal::Journal::open()
+            .and_then(|journal| journal.records())
+            .expect("journal is readable");
+        assert_eq!(records.len(), 2, "each deleted item is journaled");
     }
 
     #[test]
+    #[serial]
     fn delete_items_handles_already_deleted_targets_idempotently() {
         let temp = TempDir::new().expect("temp directory is created");
+        let _state = StateDirGuard::set(&temp.path().join("state"));
         let dir = temp.child("node_modules");
         dir.child("lib").create_dir_all().expect("directory exists");
         dir.child("lib/index.js").write_str("console.log('cache');").expect("file exists");
@@ -239,9 +390,29 @@ mod tests {
         std::fs::remove_file(file.path()).expect("pre-delete file");
 
         let progress = Arc::new(MultiProgress::new());
-        delete_items(&items, &progress, false).expect("deletion succeeds even with missing item");
+        delete_items(&items, &progress, RemovalMode::Delete, false)
+            .expect("deletion succeeds even with missing item");
 
         dir.assert(predicates::path::missing());
         file.assert(predicates::path::missing());
     }
+
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn delete_items_removes_symlink_items_without_following_them() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let _state = StateDirGuard::set(&temp.path().join("state"));
+        let output = temp.child("output/bin");
+        output.create_dir_all().expect("link target exists");
+        let link = temp.path().join("bazel-bin");
+        std::os::unix::fs::symlink(output.path(), &link).expect("link is created");
+
+        let items = vec![CleanupItem::file(Category::Native, link.clone(), 0)];
+        let progress = Arc::new(MultiProgress::new());
+        delete_items(&items, &progress, RemovalMode::Delete, false).expect("deletion succeeds");
+
+        assert!(std::fs::symlink_metadata(&link).is_err(), "the link is removed");
+        output.assert(predicates::path::is_dir());
+    }
 }
@@ -7,35 +7,68 @@ use indicatif::{MultiProgress, ProgressBar};
 use rayon::prelude::*;
 
 use crate::error::AppError;
-use crate::fs::size::path_size;
+use crate::fs::exclude::ExcludeRules;
+use crate::fs::size::{PathUsage, newest_file_modified, path_usage};
+use crate::output::format::OutputFormat;
+use crate::output::json::{
+    print_list_results_json, print_scan_report_json, print_scan_report_ndjson,
+};
 use crate::output::progress::{discovery_spinner_style, size_progress_style};
 use crate::output::report::{print_list_results, print_scan_report};
 use crate::targets::catalog;
 use crate::targets::category::Category;
-use crate::targets::item::{CleanupItem, ItemKind};
+use crate::targets::item::{AgeFilter, CleanupItem, ItemKind};
 use crate::targets::report::ScanReport;
 use crate::targets::target::ScanScope;
 
+use super::plan::Plan;
+
 pub struct ScanOptions {
     pub categories: Vec<Category>,
     pub roots: Vec<PathBuf>,
+    pub max_depth: usize,
+    pub excludes: Vec<String>,
+    pub age: AgeFilter,
     pub verbose: bool,
     pub list: bool,
     pub current: bool,
+    pub format: OutputFormat,
+    pub save_plan: Option<PathBuf>,
 }
 
 pub fn execute(options: ScanOptions) -> Result<ScanReport, AppError> {
-    let scope = ScanScope::new(options.roots, options.current, options.verbose);
+    let scope = ScanScope::new(options.roots, options.current, options.verbose)
+        .with_max_depth(options.max_depth)
+        .with_excludes(ExcludeRules::new(&options.excludes)?)
+        .with_age_filter(options.age);
 
     if options.list {
         let list_results = list_targets(&options.categories, &scope)?;
-        print_list_results(&list_results);
+        match options.format {
+            OutputFormat::Text => print_list_results(&list_results),
+            OutputFormat::Json => print_list_results_json(&list_results, false)?,
+            OutputFormat::Ndjson => print_list_results_json(&list_results, true)?,
+        }
         return Ok(ScanReport::new());
     }
 
     let progress = Arc::new(MultiProgress::new());
     let report = scan_categories(&options.categories, &scope, &progress)?;
-    print_scan_report(&report, &options.categories, options.verbose);
+    match options.format {
+        OutputFormat::Text => print_scan_report(&report, &options.categories, options.verbose),
+        OutputFormat::Json => print_scan_report_json(&report, &options.categories)?,
+        OutputFormat::Ndjson => print_scan_report_ndjson(&report, &options.categories)?,
+    }
+
+    if let Some(path) = &options.save_plan {
+        Plan::new(&options.categories, scope.roots(), report.clone()).save(path)?;
+        let message = format!("Saved deletion plan to {}", path.display());
+        if options.format == OutputFormat::Text {
+            println!("{message}")
+        } else {
+            eprintln!("{message}")
+        }
+    }
     Ok(report)
 }
 
@@ -48,7 +81,7 @@ pub fn scan_categories(
         return Ok(ScanReport::new());
     }
 
-    let targets = catalog::build_targets(categories, scope.current());
+    let targets = catalog::build_targets(categories, scope);
     if targets.is_empty() {
         if scope.current() {
             let requested_unique = catalog::unique_categories(categories.to_vec());
@@ -94,7 +127,11 @@ pub fn scan_categories(
         })
         .collect();
 
-    let mut discovered_items: Vec<CleanupItem> = discovery_results?.into_iter().flatten().collect();
+    let mut discovered_items: Vec<CleanupItem> = discovery_results?
+        .into_iter()
+        .flatten()
+        .filter(|item| !scope.is_excluded(&item.path, item.kind == ItemKind::Directory))
+        .collect();
     if discovered_items.is_empty() {
         return Ok(ScanReport::new());
     }
@@ -102,7 +139,7 @@ pub fn scan_categories(
     let total_items = discovered_items.len();
     let size_bar = progress.add(ProgressBar::new(total_items as u64));
     size_bar.set_style(size_progress_style());
-    compute_sizes_parallel(&mut discovered_items, scope.verbose(), Some(&size_bar))?;
+    compute_sizes_parallel(&mut discovered_items, scope.roots(), scope.verbose(), Some(&size_bar))?;
     size_bar.finish_and_clear();
 
     let _ = progress.println(format!(
@@ -113,6 +150,9 @@ pub fn scan_categories(
         if total_items == 1 { "" } else { "s" }
     ));
 
+    let now = chrono::Utc::now();
+    discovered_items.retain(|item| scope.age_filter().matches(item, now));
+
     let mut report = ScanReport::new();
     for item in discovered_items {
         report.add_items(item.category, vec![item]);
@@ -125,7 +165,7 @@ fn list_targets(
     categories: &[Category],
     scope: &ScanScope,
 ) -> Result<BTreeMap<Category, Vec<String>>, AppError> {
-    let targets = catalog::build_targets(categories, scope.current());
+    let targets = catalog::build_targets(categories, scope);
     if targets.is_empty() {
         return Ok(BTreeMap::new());
     }
@@ -146,25 +186,41 @@ fn list_targets(
     Ok(result_map)
 }
 
+/// Measures sizes and ages. Items found below a scan root also take their project's age from
+/// the files beside them, so a freshly edited project keeps an old build directory young.
 fn compute_sizes_parallel(
     items: &mut [CleanupItem],
+    roots: &[PathBuf],
     verbose: bool,
     progress: Option<&ProgressBar>,
 ) -> Result<(), AppError> {
     items.par_iter_mut().try_for_each(|item| {
         if item.is_zero() {
-            item.size = match item.kind {
-                ItemKind::Directory => path_size(&item.path, verbose)?,
-                ItemKind::File => match item.path.metadata() {
-                    Ok(metadata) => metadata.len(),
+            let usage = match item.kind {
+                ItemKind::Directory => path_usage(&item.path, verbose)?,
+                // Removing a link frees nothing of what it points to, so links count as empty.
+                ItemKind::File => match item.path.symlink_metadata() {
+                    Ok(metadata) => PathUsage {
+                        size: if metadata.is_symlink() { 0 } else { metadata.len() },
+                        modified: metadata.modified().ok(),
+                    },
                     Err(err) => {
                         if verbose {
                             eprintln!("Skipping {}: {}", item.path.display(), err);
                         }
-                        0
+                        PathUsage::default()
                     }
                 },
             };
+            let project_modified = item
+                .project
+                .as_deref()
+                .or_else(|| item.path.parent())
+                .filter(|_| roots.iter().any(|root| item.path.starts_with(root)))
+                .and_then(|project| newest_file_modified(project, &item.path));
+
+            item.size = usage.size;
+            item.modified = usage.modified.max(project_modified).map(Into::into);
         }
         if let Some(pb) = progress {
             pb.inc(1);
@@ -175,10 +231,13 @@ fn compute_sizes_parallel(
 
 #[cfg(test)]
 mod tests {
+    use std::time::SystemTime;
+
     use assert_fs::TempDir;
     use assert_fs::prelude::*;
 
     use crate::targets::category::Category;
+    use crate::targets::target::CleanupTarget;
 
     use super::*;
 
@@ -196,11 +255,59 @@ mod tests {
             CleanupItem::file(Category::Nodejs, file.path().to_path_buf(), 0),
         ];
 
-        compute_sizes_parallel(&mut items, false, None).expect("size calculation succeeds");
+        compute_sizes_parallel(&mut items, &[temp.path().to_path_buf()], false, None)
+            .expect("size calculation succeeds");
 
         assert!(
             items.iter().all(|item| item.size > 0),
             "expected non-zero sizes after measurement"
         );
+        assert!(items.iter().all(|item| item.modified.is_some()), "expected measured ages");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn compute_sizes_parallel_counts_symlinked_caches_as_empty() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("elsewhere/repository/artifact.jar").write_str("jar").expect("file exists");
+        let link = temp.path().join("repository");
+        std::os::unix::fs::symlink(temp.path().join("elsewhere/repository"), &link)
+            .expect("link is created");
+
+        let mut items = crate::targets::project::global_items(Category::Jvm, &[link]);
+        assert_eq!(items[0].kind, ItemKind::File);
+
+        compute_sizes_parallel(&mut items, &[], false, None).expect("size calculation succeeds");
+        assert_eq!(items[0].size, 0);
+    }
+
+    #[test]
+    fn compute_sizes_parallel_ages_nested_artifacts_by_their_project() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let app = temp.child("app");
+        app.child("pubspec.yaml").write_str("name: app").expect("marker exists");
+        app.child("ios/Podfile").write_str("platform :ios").expect("file exists");
+        app.child("ios/Pods/Manifest.lock").write_str("PODS:").expect("file exists");
+
+        let past = SystemTime::now() - Duration::from_secs(30 * 86_400);
+        for path in ["ios/Podfile", "ios/Pods/Manifest.lock", "ios/Pods"] {
+            let path = app.path().join(path);
+            std::fs::File::options()
+                .write(path.is_file())
+                .read(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(past))
+                .expect("mtime is set");
+        }
+
+        let scope = ScanScope::new(vec![temp.path().to_path_buf()], true, false);
+        let mut items = crate::targets::dart::target(true).discover(&scope).expect("scan succeeds");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].project.as_deref(), Some(app.path()));
+
+        compute_sizes_parallel(&mut items, &[temp.path().to_path_buf()], false, None)
+            .expect("size calculation succeeds");
+        let age = items[0].age(chrono::Utc::now()).expect("age is measured");
+        assert!(age < Duration::from_secs(86_400), "the fresh pubspec.yaml keeps ios/Pods young");
     }
 }
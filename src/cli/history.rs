@@ -0,0 +1,75 @@
+use chrono::{DateTime, Days, Local, NaiveDate, TimeZone, Utc};
+use clap::{ArgAction, Args};
+
+use crate::error::AppError;
+use crate::targets::category::Category;
+
+use super::duration::parse_duration;
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    #[arg(short = 't', long = "type", value_name = "CATEGORY", action = ArgAction::Append)]
+    pub categories: Vec<Category>,
+
+    #[arg(long = "since", value_name = "AGE|DATE", value_parser = parse_since, help = "Only show records newer than an age (e.g. 7d) or from a date (YYYY-MM-DD) on")]
+    pub since: Option<DateTime<Utc>>,
+
+    #[arg(long = "until", value_name = "AGE|DATE", value_parser = parse_until, help = "Only show records older than an age (e.g. 1d) or up to a date (YYYY-MM-DD), inclusive")]
+    pub until: Option<DateTime<Utc>>,
+}
+
+fn parse_since(value: &str) -> Result<DateTime<Utc>, AppError> {
+    parse_point_in_time(value, 0)
+}
+
+/// The bound is exclusive, so a date resolves to the start of the following day.
+fn parse_until(value: &str) -> Result<DateTime<Utc>, AppError> {
+    parse_point_in_time(value, 1)
+}
+
+/// Accepts either a relative age (`7d`) or a local calendar date (`2026-10-01`), which resolves
+/// to local midnight `days_after` days later.
+fn parse_point_in_time(value: &str, days_after: u64) -> Result<DateTime<Utc>, AppError> {
+    let invalid = |reason: &str| AppError::InvalidTimeBound(format!("'{value}': {reason}"));
+
+    if let Ok(age) = parse_duration(value) {
+        return chrono::Duration::from_std(age)
+            .ok()
+            .and_then(|age| Utc::now().checked_sub_signed(age))
+            .ok_or_else(|| invalid("age reaches too far into the past"));
+    }
+
+    let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| invalid("expected an age such as 7d or a date such as 2026-10-01"))?;
+    let midnight = date
+        .checked_add_days(Days::new(days_after))
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .ok_or_else(|| invalid("date is out of range"))?;
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|local| local.with_timezone(&Utc))
+        .ok_or_else(|| invalid("date does not exist in the local time zone"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ages_that_overflow_are_rejected() {
+        assert!(matches!(parse_since("100000000d"), Err(AppError::InvalidTimeBound(_))));
+        assert!(parse_since("7d").is_ok_and(|since| since < Utc::now()));
+    }
+
+    #[test]
+    fn until_date_includes_the_whole_day() {
+        let until = parse_until("2026-10-01").expect("date parses");
+        let late = Local.with_ymd_and_hms(2026, 10, 1, 23, 59, 59).unwrap().with_timezone(&Utc);
+        let next = Local.with_ymd_and_hms(2026, 10, 2, 0, 0, 0).unwrap().with_timezone(&Utc);
+
+        assert!(late < until);
+        assert_eq!(until, next);
+        assert_eq!(parse_since("2026-10-02").expect("date parses"), next);
+    }
+}
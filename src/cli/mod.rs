@@ -1,9 +1,14 @@
 use clap::{Parser, Subcommand};
 
 use crate::app;
+use crate::config::Config;
 use crate::error::AppError;
 use crate::fs::roots::resolve_roots_with_current;
 
+pub mod duration;
+pub mod history;
+pub mod purge;
+pub mod restore;
 pub mod run;
 pub mod scan;
 
@@ -26,6 +31,12 @@ enum Commands {
     /// Delete files discovered by a scan.
     #[command(visible_alias = "rn")]
     Run(run::RunArgs),
+    /// Restore an item from the quarantine, or list quarantined items.
+    Restore(restore::RestoreArgs),
+    /// Permanently delete quarantined items.
+    Purge(purge::PurgeArgs),
+    /// Show the journal of past deletions.
+    History(history::HistoryArgs),
 }
 
 pub fn run() {
@@ -36,33 +47,66 @@ pub fn run() {
 }
 
 fn run_inner() -> Result<(), AppError> {
+    // Loaded before parsing so `--type` accepts categories declared in the config file.
+    let config = Config::load();
     let cli = Cli::parse();
+    let config = config?;
 
     match cli.command {
         Commands::Scan(args) => {
-            let categories = args.resolve_categories()?;
+            let categories = args.resolve_categories(&config.scan.categories)?;
             let options = app::scan::ScanOptions {
                 categories,
-                roots: resolve_roots_with_current(&args.paths, args.current),
+                roots: resolve_roots_with_current(&args.paths, &config.scan.roots, args.current),
+                max_depth: config.scan.max_depth,
+                excludes: [config.scan.exclude.clone(), args.excludes.clone()].concat(),
+                age: args.age_filter(),
                 verbose: args.verbose,
                 list: args.list,
                 current: args.current,
+                format: args.format,
+                save_plan: args.save_plan,
             };
             app::scan::execute(options)?;
         }
         Commands::Run(args) => {
             let interactive = args.interactive();
-            let categories = args.resolve_categories()?;
+            let categories = args.resolve_categories(&config.scan.categories)?;
             let options = app::run::RunOptions {
                 categories,
                 interactive,
-                roots: resolve_roots_with_current(&args.paths, args.current),
+                roots: resolve_roots_with_current(&args.paths, &config.scan.roots, args.current),
+                max_depth: config.scan.max_depth,
+                excludes: [config.scan.exclude.clone(), args.excludes.clone()].concat(),
+                age: args.age_filter(),
                 verbose: args.verbose,
-                assume_yes: args.yes,
+                assume_yes: args.yes || !config.run.confirm,
                 current: args.current,
+                mode: args.removal_mode(config.run.mode),
+                plan: args.plan,
             };
             app::run::execute(options)?;
         }
+        Commands::Restore(args) => {
+            app::quarantine::restore(app::quarantine::RestoreOptions {
+                target: args.target,
+                verbose: args.verbose,
+            })?;
+        }
+        Commands::Purge(args) => {
+            app::quarantine::purge(app::quarantine::PurgeOptions {
+                older_than: args.older_than,
+                assume_yes: args.yes,
+                verbose: args.verbose,
+            })?;
+        }
+        Commands::History(args) => {
+            app::history::execute(app::history::HistoryOptions {
+                categories: args.categories,
+                since: args.since,
+                until: args.until,
+            })?;
+        }
     }
 
     Ok(())
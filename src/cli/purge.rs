@@ -0,0 +1,17 @@
+use std::time::Duration;
+
+use clap::{ArgAction, Args};
+
+use super::duration::parse_duration;
+
+#[derive(Args)]
+pub struct PurgeArgs {
+    #[arg(long = "older-than", value_name = "DURATION", value_parser = parse_duration, help = "Only purge items quarantined at least this long ago (e.g. 7d)")]
+    pub older_than: Option<Duration>,
+
+    #[arg(short = 'y', long = "yes", action = ArgAction::SetTrue)]
+    pub yes: bool,
+
+    #[arg(short, long, action = ArgAction::SetTrue)]
+    pub verbose: bool,
+}
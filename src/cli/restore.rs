@@ -0,0 +1,13 @@
+use clap::{ArgAction, Args};
+
+#[derive(Args)]
+pub struct RestoreArgs {
+    #[arg(
+        value_name = "ID|PATH",
+        help = "Quarantine id or original path to restore (lists quarantined items when omitted)"
+    )]
+    pub target: Option<String>,
+
+    #[arg(short, long, action = ArgAction::SetTrue)]
+    pub verbose: bool,
+}
@@ -1,17 +1,22 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::{ArgAction, Args};
 
 use crate::error::AppError;
+use crate::fs::remove::RemovalMode;
 use crate::targets::catalog;
 use crate::targets::category::Category;
+use crate::targets::item::AgeFilter;
+
+use super::duration::parse_duration;
 
 #[derive(Args)]
 pub struct RunArgs {
     #[arg(short = 't', long = "type", value_name = "CATEGORY", action = ArgAction::Append, conflicts_with = "all")]
     pub categories: Vec<Category>,
 
-    #[arg(long = "all", action = ArgAction::SetTrue, help = "Scan all supported categories (respects --current)")]
+    #[arg(long = "all", action = ArgAction::SetTrue, help = "Scan all supported categories except the opt-in rustup and runtimes (respects --current)")]
     pub all: bool,
 
     #[arg(short = 'y', long = "yes", action = ArgAction::SetTrue)]
@@ -20,19 +25,60 @@ pub struct RunArgs {
     #[arg(short, long, action = ArgAction::SetTrue)]
     pub verbose: bool,
 
+    #[arg(long = "trash", action = ArgAction::SetTrue, help = "Move items to the trash instead of deleting them permanently")]
+    pub trash: bool,
+
+    #[arg(long = "quarantine", action = ArgAction::SetTrue, conflicts_with = "trash", help = "Move items to the prf quarantine (see `prf restore` and `prf purge`)")]
+    pub quarantine: bool,
+
+    #[arg(long = "delete", action = ArgAction::SetTrue, conflicts_with_all = ["trash", "quarantine"], help = "Delete permanently even if the config file selects another mode")]
+    pub delete: bool,
+
+    #[arg(long = "exclude", value_name = "PATTERN", action = ArgAction::Append, help = "Skip paths matching a gitignore-style pattern (repeatable)")]
+    pub excludes: Vec<String>,
+
+    #[arg(long = "older-than", value_name = "DURATION", value_parser = parse_duration, conflicts_with = "plan", help = "Only include items untouched for at least this long (e.g. 30d)")]
+    pub older_than: Option<Duration>,
+
+    #[arg(long = "newer-than", value_name = "DURATION", value_parser = parse_duration, conflicts_with = "plan", help = "Only include items modified within this long (e.g. 12h)")]
+    pub newer_than: Option<Duration>,
+
     #[arg(short = 'c', long = "current", action = ArgAction::SetTrue, conflicts_with = "paths", help = "Limit cleanup to current directory only (skips Brew, Docker)")]
     pub current: bool,
 
+    #[arg(long = "plan", value_name = "FILE", conflicts_with_all = ["all", "current", "paths", "excludes"], help = "Execute a plan saved by `prf scan --save-plan` instead of rescanning")]
+    pub plan: Option<PathBuf>,
+
     #[arg(value_name = "PATH", num_args = 0..)]
     pub paths: Vec<PathBuf>,
 }
 
 impl RunArgs {
-    pub fn resolve_categories(&self) -> Result<Vec<Category>, AppError> {
-        catalog::resolve(&self.categories, self.all, self.current)
+    pub fn age_filter(&self) -> AgeFilter {
+        AgeFilter { older_than: self.older_than, newer_than: self.newer_than }
+    }
+
+    /// With `--plan`, an empty result means "every category recorded in the plan".
+    pub fn resolve_categories(&self, defaults: &[Category]) -> Result<Vec<Category>, AppError> {
+        if self.plan.is_some() {
+            return Ok(catalog::unique_categories(self.categories.clone()));
+        }
+        catalog::resolve(&self.categories, self.all, self.current, defaults)
     }
 
     pub fn interactive(&self) -> bool {
-        !self.all && self.categories.is_empty()
+        self.plan.is_none() && !self.all && self.categories.is_empty()
+    }
+
+    pub fn removal_mode(&self, default: RemovalMode) -> RemovalMode {
+        if self.quarantine {
+            RemovalMode::Quarantine
+        } else if self.trash {
+            RemovalMode::Trash
+        } else if self.delete {
+            RemovalMode::Delete
+        } else {
+            default
+        }
     }
 }
@@ -1,10 +1,15 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
 use clap::{ArgAction, Args};
 
 use crate::error::AppError;
+use crate::output::format::OutputFormat;
 use crate::targets::catalog;
 use crate::targets::category::Category;
+use crate::targets::item::AgeFilter;
+
+use super::duration::parse_duration;
 
 #[derive(Args)]
 pub struct ScanArgs {
@@ -20,6 +25,26 @@ pub struct ScanArgs {
     #[arg(long = "list", action = ArgAction::SetTrue)]
     pub list: bool,
 
+    #[arg(
+        long = "save-plan",
+        value_name = "FILE",
+        conflicts_with = "list",
+        help = "Write the scan result as a plan for `prf run --plan`"
+    )]
+    pub save_plan: Option<PathBuf>,
+
+    #[arg(long = "format", value_enum, default_value_t = OutputFormat::Text, help = "Output format for scan results")]
+    pub format: OutputFormat,
+
+    #[arg(long = "exclude", value_name = "PATTERN", action = ArgAction::Append, help = "Skip paths matching a gitignore-style pattern (repeatable)")]
+    pub excludes: Vec<String>,
+
+    #[arg(long = "older-than", value_name = "DURATION", value_parser = parse_duration, conflicts_with = "list", help = "Only include items untouched for at least this long (e.g. 30d)")]
+    pub older_than: Option<Duration>,
+
+    #[arg(long = "newer-than", value_name = "DURATION", value_parser = parse_duration, conflicts_with = "list", help = "Only include items modified within this long (e.g. 12h)")]
+    pub newer_than: Option<Duration>,
+
     #[arg(short = 'c', long = "current", action = ArgAction::SetTrue, conflicts_with = "paths")]
     pub current: bool,
 
@@ -28,7 +53,11 @@ pub struct ScanArgs {
 }
 
 impl ScanArgs {
-    pub fn resolve_categories(&self) -> Result<Vec<Category>, AppError> {
-        catalog::resolve(&self.categories, self.all, self.current)
+    pub fn age_filter(&self) -> AgeFilter {
+        AgeFilter { older_than: self.older_than, newer_than: self.newer_than }
+    }
+
+    pub fn resolve_categories(&self, defaults: &[Category]) -> Result<Vec<Category>, AppError> {
+        catalog::resolve(&self.categories, self.all, self.current, defaults)
     }
 }
@@ -0,0 +1,197 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::AppError;
+use crate::fs::remove::RemovalMode;
+use crate::targets::category::Category;
+use crate::targets::custom::{self, CustomCategory};
+
+/// Walk depth used when the config does not override it.
+pub const DEFAULT_MAX_DEPTH: usize = 10;
+
+/// User configuration loaded from `PRF_CONFIG` or `~/.config/prf/config.toml`.
+///
+/// Every field is optional; command-line flags always take precedence.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub scan: ScanConfig,
+    pub run: RunConfig,
+    /// User-defined categories declared with `[[category]]`.
+    #[serde(rename = "category")]
+    pub custom_categories: Vec<CustomCategory>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScanConfig {
+    /// Roots scanned when no PATH arguments are given (instead of `~/Desktop`).
+    pub roots: Vec<PathBuf>,
+    /// Categories used when neither `--type` nor `--all` is given.
+    pub categories: Vec<Category>,
+    /// Gitignore-style patterns that are never scanned or deleted, including everything below.
+    pub exclude: Vec<String>,
+    /// Maximum directory depth walked below each root.
+    pub max_depth: usize,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            categories: Vec::new(),
+            exclude: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunConfig {
+    /// Ask before deleting; `false` behaves as if `-y/--yes` were always passed.
+    pub confirm: bool,
+    /// Default removal mode: `delete`, `trash`, or `quarantine`.
+    pub mode: RemovalMode,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self { confirm: true, mode: RemovalMode::Delete }
+    }
+}
+
+impl Config {
+    /// Loads the config file and registers its custom categories. A missing default file yields
+    /// the built-in defaults, while a missing file named by `PRF_CONFIG` is an error.
+    pub fn load() -> Result<Self, AppError> {
+        let config = Self::read()?;
+        custom::register(config.custom_categories.clone())?;
+
+        if let Some(unknown) = config.scan.categories.iter().find(|category| !category.is_defined())
+        {
+            return Err(AppError::Config(format!(
+                "unknown category '{unknown}' in scan.categories"
+            )));
+        }
+        Ok(config)
+    }
+
+    fn read() -> Result<Self, AppError> {
+        if let Some(explicit) = std::env::var_os("PRF_CONFIG").filter(|value| !value.is_empty()) {
+            return Self::from_file(Path::new(&explicit));
+        }
+
+        let Some(path) = default_path() else {
+            return Ok(Self::default());
+        };
+        match Self::from_file(&path) {
+            Err(AppError::Io(err)) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            result => result,
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, AppError> {
+        let contents = fs::read_to_string(path)?;
+        let mut config: Config = toml::from_str(&contents)
+            .map_err(|err| AppError::Config(format!("{}: {}", path.display(), err.message())))?;
+
+        config.scan.roots = config.scan.roots.iter().map(|path| expand_home(path)).collect();
+        for definition in &mut config.custom_categories {
+            definition.paths = definition.paths.iter().map(|path| expand_home(path)).collect();
+        }
+        Ok(config)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/prf/config.toml`, falling back to `~/.config/prf/config.toml`.
+pub fn default_path() -> Option<PathBuf> {
+    if let Some(config_home) = std::env::var_os("XDG_CONFIG_HOME").filter(|value| !value.is_empty())
+    {
+        return Some(PathBuf::from(config_home).join("prf/config.toml"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/prf/config.toml"))
+}
+
+/// Expands a leading `~` to the home directory.
+pub fn expand_home(path: &Path) -> PathBuf {
+    let Ok(rest) = path.strip_prefix("~") else {
+        return path.to_path_buf();
+    };
+    match std::env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(rest),
+        None => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn from_file_parses_all_sections() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let file = temp.child("config.toml");
+        file.write_str(
+            r#"
+[scan]
+roots = ["/src", "/work"]
+categories = ["rust", "nodejs"]
+exclude = ["/src/vendored"]
+max_depth = 4
+
+[run]
+confirm = false
+mode = "trash"
+
+[[category]]
+name = "gen"
+display_name = "Generated"
+names = [".gen-cache", "out-bundle"]
+marker = "gen.yaml"
+"#,
+        )
+        .expect("config is written");
+
+        let config = Config::from_file(file.path()).expect("config parses");
+        assert_eq!(config.scan.roots, vec![PathBuf::from("/src"), PathBuf::from("/work")]);
+        assert_eq!(config.scan.categories, vec![Category::Rust, Category::Nodejs]);
+        assert_eq!(config.scan.exclude, vec!["/src/vendored"]);
+        assert_eq!(config.scan.max_depth, 4);
+        assert!(!config.run.confirm);
+        assert_eq!(config.run.mode, RemovalMode::Trash);
+        assert_eq!(config.custom_categories.len(), 1);
+        assert_eq!(config.custom_categories[0].names, vec![".gen-cache", "out-bundle"]);
+        assert_eq!(config.custom_categories[0].marker.as_deref(), Some("gen.yaml"));
+    }
+
+    #[test]
+    fn from_file_defaults_missing_fields_and_rejects_unknown_keys() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let file = temp.child("config.toml");
+        file.write_str("[scan]\nroots = [\"/src\"]\n").expect("config is written");
+
+        let config = Config::from_file(file.path()).expect("config parses");
+        assert_eq!(config.scan.max_depth, DEFAULT_MAX_DEPTH);
+        assert!(config.run.confirm);
+        assert_eq!(config.run.mode, RemovalMode::Delete);
+
+        file.write_str("[scan]\nroot = [\"/src\"]\n").expect("config is written");
+        assert!(matches!(Config::from_file(file.path()), Err(AppError::Config(_))));
+    }
+
+    #[test]
+    fn expand_home_only_rewrites_leading_tilde() {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        if let Some(home) = home {
+            assert_eq!(expand_home(Path::new("~/src")), home.join("src"));
+        }
+        assert_eq!(expand_home(Path::new("/tmp/~x")), PathBuf::from("/tmp/~x"));
+    }
+}
@@ -8,6 +8,9 @@ pub enum AppError {
     #[error("I/O error: {0}")]
     Io(#[from] io::Error),
 
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
     #[error("Unknown category '{0}'")]
     InvalidCategory(String),
 
@@ -20,6 +23,24 @@ pub enum AppError {
     #[error("No targets to scan: {0}")]
     NoTargetsToScan(String),
 
+    #[error("No quarantined item matches '{0}'")]
+    QuarantineEntryNotFound(String),
+
+    #[error("Cannot restore {0}: destination already exists")]
+    RestoreTargetExists(String),
+
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    #[error("Invalid exclude pattern: {0}")]
+    InvalidExcludePattern(String),
+
+    #[error("Invalid plan: {0}")]
+    InvalidPlan(String),
+
+    #[error("Invalid time bound: {0}")]
+    InvalidTimeBound(String),
+
     #[error("Operation cancelled by user")]
     Cancelled,
 }
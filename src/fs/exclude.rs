@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use ignore::Match;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::config::expand_home;
+use crate::error::AppError;
+
+/// Per-directory exclusion file, read with gitignore syntax.
+pub const IGNORE_FILE: &str = ".prfignore";
+
+/// Gitignore-style patterns from `--exclude` and the config file, matched against absolute paths.
+///
+/// Patterns without a slash match at any depth; patterns with one are anchored, so absolute
+/// paths (`/…` or `~/…`) match exactly that location and `**/` matches at any depth.
+#[derive(Debug, Clone)]
+pub struct ExcludeRules {
+    global: Gitignore,
+}
+
+impl Default for ExcludeRules {
+    fn default() -> Self {
+        Self { global: Gitignore::empty() }
+    }
+}
+
+impl ExcludeRules {
+    pub fn new(patterns: &[String]) -> Result<Self, AppError> {
+        let mut builder = GitignoreBuilder::new("/");
+        for pattern in patterns {
+            let expanded = expand_home(Path::new(pattern));
+            builder
+                .add_line(None, &expanded.to_string_lossy())
+                .map_err(|err| AppError::InvalidExcludePattern(format!("{pattern}: {err}")))?;
+        }
+        let global =
+            builder.build().map_err(|err| AppError::InvalidExcludePattern(err.to_string()))?;
+        Ok(Self { global })
+    }
+
+    /// Whether an absolute `path`, or any of its parents, matches a global pattern.
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        path.is_absolute() && self.global.matched_path_or_any_parents(path, is_dir).is_ignore()
+    }
+}
+
+/// `.prfignore` matchers discovered during one walk, keyed by the directory that holds them.
+#[derive(Default)]
+pub struct LocalRules {
+    matchers: HashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl LocalRules {
+    /// Checks `path` against every `.prfignore` between `root` and the path's parent. The
+    /// deepest file with a matching pattern decides, so nested files can re-include with `!`.
+    pub fn is_excluded(&mut self, root: &Path, path: &Path, is_dir: bool) -> bool {
+        let Some(parent) = path.parent() else {
+            return false;
+        };
+
+        for dir in parent.ancestors() {
+            if !dir.starts_with(root) {
+                break;
+            }
+            let matcher = self.matchers.entry(dir.to_path_buf()).or_insert_with(|| load(dir));
+            match matcher.as_ref().map_or(Match::None, |matcher| matcher.matched(path, is_dir)) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+
+        false
+    }
+}
+
+fn load(dir: &Path) -> Option<Gitignore> {
+    let file = dir.join(IGNORE_FILE);
+    if !file.is_file() {
+        return None;
+    }
+
+    let mut builder = GitignoreBuilder::new(dir);
+    if let Some(err) = builder.add(&file) {
+        eprintln!("Warning: {}: {err}", file.display());
+    }
+    match builder.build() {
+        Ok(matcher) => Some(matcher),
+        Err(err) => {
+            eprintln!("Warning: ignoring {}: {err}", file.display());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn global_patterns_match_names_anywhere_and_anchored_paths_exactly() {
+        let rules = ExcludeRules::new(&["node_modules".to_string(), "/work/app/.venv".to_string()])
+            .expect("patterns are valid");
+
+        assert!(rules.is_excluded(Path::new("/src/web/node_modules"), true));
+        assert!(rules.is_excluded(Path::new("/src/web/node_modules/pkg"), true));
+        assert!(rules.is_excluded(Path::new("/work/app/.venv"), true));
+        assert!(!rules.is_excluded(Path::new("/work/other/.venv"), true));
+        assert!(!ExcludeRules::default().is_excluded(Path::new("/src/node_modules"), true));
+    }
+
+    #[test]
+    fn local_rules_apply_below_their_directory_and_allow_reinclusion() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("repo/.prfignore").write_str("node_modules\n").expect("ignore file exists");
+        temp.child("repo/web/.prfignore").write_str("!node_modules\n").expect("ignore file exists");
+        let root = temp.path();
+
+        let mut local = LocalRules::default();
+        assert!(local.is_excluded(root, &root.join("repo/api/node_modules"), true));
+        assert!(!local.is_excluded(root, &root.join("repo/web/node_modules"), true));
+        assert!(!local.is_excluded(root, &root.join("other/node_modules"), true));
+    }
+}
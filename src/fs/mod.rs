@@ -1,3 +1,6 @@
+pub mod exclude;
+pub mod relocate;
 pub mod remove;
 pub mod roots;
 pub mod size;
+pub mod trash;
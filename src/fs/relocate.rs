@@ -0,0 +1,75 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::error::AppError;
+
+use super::remove::safe_remove_dir_all;
+
+/// Renames `source` to `destination`, falling back to copy-and-delete when the destination
+/// lives on a different filesystem.
+pub fn move_path(source: &Path, destination: &Path, verbose: bool) -> Result<(), AppError> {
+    match fs::rename(source, destination) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices => {
+            if verbose {
+                eprintln!("{} is on another device; copying instead", source.display());
+            }
+            copy_recursively(source, destination)?;
+            let metadata = fs::symlink_metadata(source)?;
+            if metadata.is_dir() {
+                safe_remove_dir_all(source, verbose)
+            } else {
+                fs::remove_file(source).map_err(AppError::Io)
+            }
+        }
+        Err(err) => Err(AppError::Io(err)),
+    }
+}
+
+fn copy_recursively(source: &Path, destination: &Path) -> Result<(), AppError> {
+    let metadata = fs::symlink_metadata(source)?;
+
+    if metadata.file_type().is_symlink() {
+        let link = fs::read_link(source)?;
+        std::os::unix::fs::symlink(link, destination)?;
+    } else if metadata.is_dir() {
+        fs::create_dir(destination)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_recursively(&entry.path(), &destination.join(entry.file_name()))?;
+        }
+        fs::set_permissions(destination, metadata.permissions())?;
+    } else {
+        fs::copy(source, destination)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn copy_recursively_preserves_tree_and_symlinks() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let source = temp.child("source");
+        source.child("lib/index.js").write_str("console.log('cache');").expect("file exists");
+        std::os::unix::fs::symlink("lib/index.js", source.child("link").path())
+            .expect("symlink exists");
+
+        let destination = temp.child("destination");
+        copy_recursively(source.path(), destination.path()).expect("copy succeeds");
+
+        destination.child("lib/index.js").assert("console.log('cache');");
+        assert_eq!(
+            fs::read_link(destination.child("link").path()).expect("link is copied"),
+            PathBuf::from("lib/index.js")
+        );
+    }
+}
@@ -2,11 +2,25 @@ use std::fs;
 use std::io;
 use std::path::Path;
 
+use serde::Deserialize;
 use walkdir::WalkDir;
 
 use crate::error::AppError;
 use crate::targets::item::ItemKind;
 
+/// How `prf run` disposes of selected items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemovalMode {
+    /// Unlink items permanently.
+    #[default]
+    Delete,
+    /// Move items into the platform trash so they can be restored from Finder or the file manager.
+    Trash,
+    /// Move items into the prf quarantine until `prf purge` or `prf restore`.
+    Quarantine,
+}
+
 pub fn remove_item(path: &Path, kind: ItemKind, verbose: bool) -> Result<(), AppError> {
     match kind {
         ItemKind::Directory => safe_remove_dir_all(path, verbose),
@@ -41,7 +55,7 @@ pub fn safe_remove_dir_all(path: &Path, verbose: bool) -> Result<(), AppError> {
     }
 
     for file in &files_to_remove {
-        match fs::remove_file(file) {
+        match remove_granting_write(path, file, |path| fs::remove_file(path)) {
             Ok(()) => {}
             Err(err) if err.kind() == io::ErrorKind::NotFound => {}
             Err(err) => return Err(AppError::Io(err)),
@@ -50,7 +64,7 @@ pub fn safe_remove_dir_all(path: &Path, verbose: bool) -> Result<(), AppError> {
 
     dirs_to_remove.sort_by_key(|(depth, _)| std::cmp::Reverse(*depth));
     for (_, dir) in &dirs_to_remove {
-        match fs::remove_dir(dir) {
+        match remove_granting_write(path, dir, |path| fs::remove_dir(path)) {
             Ok(()) => {}
             Err(err) if err.kind() == io::ErrorKind::NotFound => {}
             Err(err) if err.kind() == io::ErrorKind::DirectoryNotEmpty => {
@@ -67,3 +81,84 @@ pub fn safe_remove_dir_all(path: &Path, verbose: bool) -> Result<(), AppError> {
 
     Ok(())
 }
+
+/// Runs `remove`, retrying once after giving the owner write access to the parent directory
+/// when that directory lies inside `root`, the tree being deleted. Tools such as Go write their
+/// caches as read-only trees, which otherwise cannot be unlinked; permissions outside the tree
+/// are never touched.
+fn remove_granting_write(
+    root: &Path,
+    path: &Path,
+    remove: fn(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    match remove(path) {
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => {
+            let Some(parent) = path.parent().filter(|parent| parent.starts_with(root)) else {
+                return Err(err);
+            };
+            if make_owner_writable(parent).is_err() {
+                return Err(err);
+            }
+            remove(path)
+        }
+        result => result,
+    }
+}
+
+#[cfg(unix)]
+fn make_owner_writable(dir: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(dir)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o700);
+    fs::set_permissions(dir, permissions)
+}
+
+#[cfg(not(unix))]
+fn make_owner_writable(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn safe_remove_dir_all_removes_read_only_trees() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().expect("temp directory is created");
+        let module = temp.child("pkg/mod/example.com/lib@v1.0.0");
+        module.child("lib.go").write_str("package lib").expect("file exists");
+        for path in [module.child("lib.go").path(), module.path(), temp.child("pkg/mod").path()] {
+            let mode = if path.is_dir() { 0o555 } else { 0o444 };
+            fs::set_permissions(path, fs::Permissions::from_mode(mode)).expect("mode is set");
+        }
+
+        safe_remove_dir_all(&temp.path().join("pkg/mod"), false)
+            .expect("read-only tree is removed");
+        temp.child("pkg/mod").assert(predicates::path::missing());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn safe_remove_dir_all_leaves_permissions_outside_the_tree_alone() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("locked/cache/entry").write_str("data").expect("file exists");
+        let locked = temp.child("locked");
+        fs::set_permissions(locked.path(), fs::Permissions::from_mode(0o555)).expect("mode is set");
+
+        let _ = safe_remove_dir_all(&locked.path().join("cache"), false);
+        let mode = fs::metadata(locked.path()).expect("parent exists").permissions().mode();
+        fs::set_permissions(locked.path(), fs::Permissions::from_mode(0o755)).expect("mode is set");
+
+        assert_eq!(mode & 0o777, 0o555);
+        locked.child("cache/entry").assert(predicates::path::missing());
+    }
+}
@@ -1,24 +1,29 @@
 use std::path::PathBuf;
 
-pub fn resolve_roots(explicit: &[PathBuf]) -> Vec<PathBuf> {
-    if explicit.is_empty() {
-        if let Some(home) = std::env::var_os("HOME").map(PathBuf::from) {
-            vec![home.join("Desktop")]
-        } else {
-            vec![std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))]
-        }
-    } else {
+/// Explicit roots win, then roots from the config file, then `~/Desktop`.
+pub fn resolve_roots(explicit: &[PathBuf], configured: &[PathBuf]) -> Vec<PathBuf> {
+    if !explicit.is_empty() {
         explicit.to_vec()
+    } else if !configured.is_empty() {
+        configured.to_vec()
+    } else if let Some(home) = std::env::var_os("HOME").map(PathBuf::from) {
+        vec![home.join("Desktop")]
+    } else {
+        vec![std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))]
     }
 }
 
-pub fn resolve_roots_with_current(explicit: &[PathBuf], current: bool) -> Vec<PathBuf> {
+pub fn resolve_roots_with_current(
+    explicit: &[PathBuf],
+    configured: &[PathBuf],
+    current: bool,
+) -> Vec<PathBuf> {
     debug_assert!(!current || explicit.is_empty());
 
     if current {
         vec![std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))]
     } else {
-        resolve_roots(explicit)
+        resolve_roots(explicit, configured)
     }
 }
 
@@ -61,7 +66,13 @@ mod tests {
     #[test]
     fn resolve_roots_returns_explicit_roots_when_non_empty() {
         let explicit = vec![PathBuf::from("/tmp/a"), PathBuf::from("/tmp/b")];
-        assert_eq!(resolve_roots(&explicit), explicit);
+        assert_eq!(resolve_roots(&explicit, &[PathBuf::from("/tmp/c")]), explicit);
+    }
+
+    #[test]
+    fn resolve_roots_prefers_configured_roots_over_desktop() {
+        let configured = vec![PathBuf::from("/src"), PathBuf::from("/work")];
+        assert_eq!(resolve_roots(&[], &configured), configured);
     }
 
     #[test]
@@ -74,7 +85,7 @@ mod tests {
             std::env::set_var("HOME", temp_home.path());
         }
 
-        let roots = resolve_roots(&[]);
+        let roots = resolve_roots(&[], &[]);
         assert_eq!(roots, vec![temp_home.path().join("Desktop")]);
     }
 
@@ -85,7 +96,7 @@ mod tests {
         let temp = TempDir::new().expect("temp directory is created");
         std::env::set_current_dir(temp.path()).expect("cwd is set");
 
-        let roots = resolve_roots_with_current(&[], true);
+        let roots = resolve_roots_with_current(&[], &[PathBuf::from("/tmp/c")], true);
         let expected = std::env::current_dir().expect("cwd resolves");
         assert_eq!(roots, vec![expected]);
     }
@@ -101,7 +112,7 @@ mod tests {
             std::env::remove_var("HOME");
         }
 
-        let roots = resolve_roots(&[]);
+        let roots = resolve_roots(&[], &[]);
         let expected = std::env::current_dir().expect("cwd resolves");
         assert_eq!(roots, vec![expected]);
     }
@@ -1,12 +1,39 @@
+use std::fs;
 use std::path::Path;
+use std::time::SystemTime;
 
 use walkdir::WalkDir;
 
 use crate::error::AppError;
 
+/// Total size of a path together with the newest modification time found inside it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathUsage {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl PathUsage {
+    fn record_modified(&mut self, metadata: &fs::Metadata) {
+        if let Ok(modified) = metadata.modified() {
+            self.modified = self.modified.max(Some(modified));
+        }
+    }
+}
+
 pub fn path_size(path: &Path, verbose: bool) -> Result<u64, AppError> {
+    path_usage(path, verbose).map(|usage| usage.size)
+}
+
+/// Measures `path`, counting file sizes and taking the newest modification time of every file
+/// and directory within it (including `path` itself).
+pub fn path_usage(path: &Path, verbose: bool) -> Result<PathUsage, AppError> {
+    let mut usage = PathUsage::default();
     if path.is_file() {
-        Ok(path.metadata()?.len())
+        let metadata = path.metadata()?;
+        usage.size = metadata.len();
+        usage.record_modified(&metadata);
+        Ok(usage)
     } else {
         if !path.try_exists()? {
             return Err(AppError::Io(std::io::Error::new(
@@ -16,7 +43,6 @@ pub fn path_size(path: &Path, verbose: bool) -> Result<u64, AppError> {
         }
         let _ = path.metadata()?;
 
-        let mut total = 0u64;
         for entry in WalkDir::new(path).into_iter() {
             let entry = match entry {
                 Ok(entry) => entry,
@@ -28,19 +54,68 @@ pub fn path_size(path: &Path, verbose: bool) -> Result<u64, AppError> {
                 }
             };
 
-            if entry.file_type().is_file() {
-                match entry.metadata() {
-                    Ok(metadata) => {
-                        total = total.saturating_add(metadata.len());
+            match entry.metadata() {
+                Ok(metadata) => {
+                    if entry.file_type().is_file() {
+                        usage.size = usage.size.saturating_add(metadata.len());
                     }
-                    Err(err) => {
-                        if verbose {
-                            eprintln!("Skipping {}: {}", entry.path().display(), err);
-                        }
+                    usage.record_modified(&metadata);
+                }
+                Err(err) => {
+                    if verbose {
+                        eprintln!("Skipping {}: {}", entry.path().display(), err);
                     }
                 }
             }
         }
-        Ok(total)
+        Ok(usage)
+    }
+}
+
+/// Newest modification time among the regular files directly inside `dir` (a project's
+/// manifests, lockfiles, and top-level sources), ignoring `exclude`.
+pub fn newest_file_modified(dir: &Path, exclude: &Path) -> Option<SystemTime> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path() != exclude)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .filter_map(|metadata| metadata.modified().ok())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn path_usage_reports_size_and_newest_modification() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let dir = temp.child("target");
+        let old = dir.child("debug/old.rlib");
+        old.write_str("old").expect("file exists");
+        let new = dir.child("debug/new.rlib");
+        new.write_str("newer").expect("file exists");
+
+        let past = SystemTime::now() - Duration::from_secs(30 * 86_400);
+        for path in [old.path(), dir.child("debug").path(), dir.path()] {
+            fs::File::options()
+                .write(path.is_file())
+                .read(true)
+                .open(path)
+                .and_then(|file| file.set_modified(past))
+                .expect("mtime is set");
+        }
+        let newest = fs::metadata(new.path()).and_then(|meta| meta.modified()).expect("mtime");
+
+        let usage = path_usage(dir.path(), false).expect("usage is measured");
+        assert_eq!(usage.size, 8);
+        assert_eq!(usage.modified, Some(newest));
     }
 }
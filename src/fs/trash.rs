@@ -0,0 +1,301 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::AppError;
+
+use super::relocate::move_path;
+
+// Destinations claimed by moves still in flight, so parallel deletions of identically named
+// items (e.g. several `node_modules`) never race for the same slot. The lock is held only while
+// a name is chosen; the move itself, which may be a slow cross-device copy, runs outside it.
+static RESERVED: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+fn reserved() -> MutexGuard<'static, BTreeSet<PathBuf>> {
+    RESERVED.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn move_to_trash(path: &Path, verbose: bool) -> Result<(), AppError> {
+    match fs::symlink_metadata(path) {
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(AppError::Io(err)),
+    }
+
+    let slot = {
+        let mut reserved = reserved();
+        let slot = platform::reserve_slot(path, &reserved)?;
+        reserved.insert(slot.destination.clone());
+        slot
+    };
+    let result = platform::move_into_slot(path, &slot, verbose);
+    reserved().remove(&slot.destination);
+    result?;
+
+    if verbose {
+        println!("Moved {} to {}", path.display(), slot.destination.display());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::collections::BTreeSet;
+    use std::path::{Path, PathBuf};
+
+    use dirs_next as dirs;
+
+    use crate::error::AppError;
+
+    use super::{candidate_name, file_name_of, move_path};
+
+    pub(super) struct Slot {
+        pub(super) destination: PathBuf,
+    }
+
+    pub(super) fn reserve_slot(
+        path: &Path,
+        reserved: &BTreeSet<PathBuf>,
+    ) -> Result<Slot, AppError> {
+        let home = dirs::home_dir().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "home directory not found")
+        })?;
+        let trash_dir = home.join(".Trash");
+        std::fs::create_dir_all(&trash_dir)?;
+
+        let file_name = file_name_of(path)?;
+        let mut attempt = 1;
+        loop {
+            let candidate = trash_dir.join(candidate_name(&file_name, attempt));
+            if std::fs::symlink_metadata(&candidate).is_err() && !reserved.contains(&candidate) {
+                return Ok(Slot { destination: candidate });
+            }
+            attempt += 1;
+        }
+    }
+
+    pub(super) fn move_into_slot(path: &Path, slot: &Slot, verbose: bool) -> Result<(), AppError> {
+        move_path(path, &slot.destination, verbose)
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use std::collections::BTreeSet;
+    use std::fs::{self, OpenOptions};
+    use std::io::{self, Write};
+    use std::path::{Path, PathBuf};
+
+    use dirs_next as dirs;
+
+    use crate::error::AppError;
+
+    use super::{candidate_name, file_name_of, move_path};
+
+    /// Home trash as defined by the freedesktop.org Trash specification.
+    fn trash_home() -> Result<PathBuf, AppError> {
+        if let Some(data_home) = std::env::var_os("XDG_DATA_HOME").filter(|value| !value.is_empty())
+        {
+            return Ok(PathBuf::from(data_home).join("Trash"));
+        }
+
+        let home = dirs::home_dir()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "home directory not found"))?;
+        Ok(home.join(".local/share/Trash"))
+    }
+
+    pub(super) struct Slot {
+        pub(super) destination: PathBuf,
+        info_path: PathBuf,
+    }
+
+    pub(super) fn reserve_slot(
+        path: &Path,
+        reserved: &BTreeSet<PathBuf>,
+    ) -> Result<Slot, AppError> {
+        let trash = trash_home()?;
+        let files_dir = trash.join("files");
+        let info_dir = trash.join("info");
+        fs::create_dir_all(&files_dir)?;
+        fs::create_dir_all(&info_dir)?;
+
+        let original = std::path::absolute(path)?;
+        let file_name = file_name_of(path)?;
+        let info = format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            encode_path(&original),
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+        );
+
+        // The spec requires the .trashinfo file to be created atomically first; it acts as
+        // the reservation for the name in `files/`.
+        let mut attempt = 1;
+        loop {
+            let name = candidate_name(&file_name, attempt);
+            attempt += 1;
+
+            let destination = files_dir.join(&name);
+            if fs::symlink_metadata(&destination).is_ok() || reserved.contains(&destination) {
+                continue;
+            }
+
+            let info_path = info_dir.join(format!("{name}.trashinfo"));
+            let mut info_file =
+                match OpenOptions::new().write(true).create_new(true).open(&info_path) {
+                    Ok(file) => file,
+                    Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+                    Err(err) => return Err(AppError::Io(err)),
+                };
+            if let Err(err) = info_file.write_all(info.as_bytes()) {
+                let _ = fs::remove_file(&info_path);
+                return Err(AppError::Io(err));
+            }
+            return Ok(Slot { destination, info_path });
+        }
+    }
+
+    /// Moves `path` into its reserved slot, releasing the `.trashinfo` reservation on failure.
+    pub(super) fn move_into_slot(path: &Path, slot: &Slot, verbose: bool) -> Result<(), AppError> {
+        if let Err(err) = move_path(path, &slot.destination, verbose) {
+            let _ = fs::remove_file(&slot.info_path);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn encode_path(path: &Path) -> String {
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut encoded = String::new();
+        for byte in path.as_os_str().as_bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                    encoded.push(*byte as char)
+                }
+                _ => encoded.push_str(&format!("%{byte:02X}")),
+            }
+        }
+        encoded
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn encode_path_escapes_reserved_characters() {
+            assert_eq!(encode_path(Path::new("/tmp/my cache/%x")), "/tmp/my%20cache/%25x");
+        }
+    }
+}
+
+fn file_name_of(path: &Path) -> Result<String, AppError> {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).ok_or_else(|| {
+        AppError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot move {} to the trash", path.display()),
+        ))
+    })
+}
+
+/// Builds the n-th candidate name using the Finder convention (`name`, `name 2`, `name 3.ext`).
+fn candidate_name(file_name: &str, attempt: usize) -> String {
+    if attempt <= 1 {
+        return file_name.to_string();
+    }
+
+    match file_name.rfind('.') {
+        Some(index) if index > 0 => {
+            format!("{} {}{}", &file_name[..index], attempt, &file_name[index..])
+        }
+        _ => format!("{file_name} {attempt}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn candidate_name_follows_finder_convention() {
+        assert_eq!(candidate_name("node_modules", 1), "node_modules");
+        assert_eq!(candidate_name("node_modules", 2), "node_modules 2");
+        assert_eq!(candidate_name("cache.log", 3), "cache 3.log");
+        assert_eq!(candidate_name(".venv", 2), ".venv 2");
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    #[serial_test::serial]
+    fn move_to_trash_records_trashinfo_and_resolves_collisions() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let data_home = temp.child("data");
+        let original_data_home = std::env::var_os("XDG_DATA_HOME");
+        unsafe {
+            std::env::set_var("XDG_DATA_HOME", data_home.path());
+        }
+
+        let first = temp.child("a/node_modules");
+        first.child("index.js").write_str("a").expect("file exists");
+        let second = temp.child("b/node_modules");
+        second.child("index.js").write_str("b").expect("file exists");
+
+        let first_result = move_to_trash(first.path(), false);
+        let second_result = move_to_trash(second.path(), false);
+
+        unsafe {
+            match original_data_home {
+                Some(value) => std::env::set_var("XDG_DATA_HOME", value),
+                None => std::env::remove_var("XDG_DATA_HOME"),
+            }
+        }
+
+        first_result.expect("first item is trashed");
+        second_result.expect("second item is trashed");
+
+        first.assert(predicates::path::missing());
+        second.assert(predicates::path::missing());
+        data_home.child("Trash/files/node_modules/index.js").assert("a");
+        data_home.child("Trash/files/node_modules 2/index.js").assert("b");
+
+        let info = fs::read_to_string(data_home.child("Trash/info/node_modules 2.trashinfo"))
+            .expect("trashinfo is written");
+        assert!(info.starts_with("[Trash Info]\n"));
+        assert!(info.contains(&format!("Path={}", second.path().display())));
+        assert!(info.contains("DeletionDate="));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    #[serial_test::serial]
+    fn reserve_slot_skips_destinations_of_moves_in_flight() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let data_home = temp.child("data");
+        let original_data_home = std::env::var_os("XDG_DATA_HOME");
+        unsafe {
+            std::env::set_var("XDG_DATA_HOME", data_home.path());
+        }
+
+        let item = temp.child("a/node_modules");
+        item.create_dir_all().expect("directory exists");
+        let in_flight = BTreeSet::from([data_home.path().join("Trash/files/node_modules")]);
+        let slot = platform::reserve_slot(item.path(), &in_flight);
+
+        unsafe {
+            match original_data_home {
+                Some(value) => std::env::set_var("XDG_DATA_HOME", value),
+                None => std::env::remove_var("XDG_DATA_HOME"),
+            }
+        }
+
+        let slot = slot.expect("a slot is reserved");
+        assert_eq!(slot.destination, data_home.path().join("Trash/files/node_modules 2"));
+        data_home.child("Trash/info/node_modules 2.trashinfo").assert(predicates::path::exists());
+        item.assert(predicates::path::exists());
+    }
+}
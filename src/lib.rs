@@ -1,9 +1,11 @@
 //! Library entry point for the prf CLI (purify).
 
 pub mod app;
+pub mod config;
 pub mod error;
 pub mod fs;
 pub mod output;
+pub mod state;
 pub mod targets;
 
 #[path = "cli/mod.rs"]
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+/// Formats an age with the largest whole unit, using the units accepted by `--older-than`.
+pub fn format_age(age: Duration) -> String {
+    const UNITS: [(u64, &str); 5] =
+        [(7 * 24 * 60 * 60, "w"), (24 * 60 * 60, "d"), (60 * 60, "h"), (60, "m"), (1, "s")];
+
+    let seconds = age.as_secs();
+    let (unit_seconds, unit) = UNITS
+        .iter()
+        .copied()
+        .find(|(unit_seconds, _)| seconds >= *unit_seconds)
+        .unwrap_or((1, "s"));
+    format!("{}{unit}", seconds / unit_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_age_uses_largest_whole_unit() {
+        assert_eq!(format_age(Duration::from_secs(0)), "0s");
+        assert_eq!(format_age(Duration::from_secs(90)), "1m");
+        assert_eq!(format_age(Duration::from_secs(5 * 3600 + 59)), "5h");
+        assert_eq!(format_age(Duration::from_secs(13 * 86_400)), "1w");
+        assert_eq!(format_age(Duration::from_secs(6 * 86_400)), "6d");
+    }
+}
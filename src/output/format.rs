@@ -0,0 +1,13 @@
+use clap::ValueEnum;
+
+/// Rendering used for scan results on stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable summary.
+    #[default]
+    Text,
+    /// A single JSON document.
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::targets::category::Category;
+use crate::targets::item::CleanupItem;
+use crate::targets::report::ScanReport;
+
+#[derive(Serialize)]
+struct ScanReportView<'a> {
+    total_size: u64,
+    categories: Vec<CategoryReportView<'a>>,
+}
+
+#[derive(Serialize)]
+struct CategoryReportView<'a> {
+    category: Category,
+    display_name: &'static str,
+    total_size: u64,
+    items: &'a [CleanupItem],
+}
+
+#[derive(Serialize)]
+struct ListEntryView<'a> {
+    category: Category,
+    target: &'a str,
+}
+
+/// Writes the report as one JSON document with raw byte counts.
+pub fn print_scan_report_json(
+    report: &ScanReport,
+    categories: &[Category],
+) -> Result<(), AppError> {
+    let view = ScanReportView {
+        total_size: report.total_size(),
+        categories: categories
+            .iter()
+            .map(|category| {
+                let category_report = report.report_for(*category);
+                CategoryReportView {
+                    category: *category,
+                    display_name: category.display_name(),
+                    total_size: category_report.map(|value| value.total_size()).unwrap_or(0),
+                    items: category_report.map(|value| value.items.as_slice()).unwrap_or(&[]),
+                }
+            })
+            .collect(),
+    };
+
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, &view)?;
+    writeln!(stdout)?;
+    Ok(())
+}
+
+/// Writes one JSON object per cleanup item.
+pub fn print_scan_report_ndjson(
+    report: &ScanReport,
+    categories: &[Category],
+) -> Result<(), AppError> {
+    let mut stdout = io::stdout().lock();
+    for category in categories {
+        if let Some(category_report) = report.report_for(*category) {
+            for item in &category_report.items {
+                serde_json::to_writer(&mut stdout, item)?;
+                writeln!(stdout)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_list_results_json(
+    results: &BTreeMap<Category, Vec<String>>,
+    ndjson: bool,
+) -> Result<(), AppError> {
+    let mut stdout = io::stdout().lock();
+    if ndjson {
+        for (category, targets) in results {
+            for target in targets {
+                serde_json::to_writer(&mut stdout, &ListEntryView { category: *category, target })?;
+                writeln!(stdout)?;
+            }
+        }
+    } else {
+        serde_json::to_writer_pretty(&mut stdout, results)?;
+        writeln!(stdout)?;
+    }
+    Ok(())
+}
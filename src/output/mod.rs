@@ -1,4 +1,7 @@
+pub mod age;
 pub mod bytes;
+pub mod format;
+pub mod json;
 pub mod progress;
 pub mod prompt;
 pub mod report;
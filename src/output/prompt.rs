@@ -1,10 +1,263 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+
+use chrono::{DateTime, Utc};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{MultiSelect, Select};
 
 use crate::error::AppError;
 use crate::targets::category::Category;
+use crate::targets::item::CleanupItem;
 use crate::targets::report::ScanReport;
 
+use super::age::format_age;
 use super::bytes::format_bytes;
+use super::report::display_path;
+
+/// Whether the checklist UI can be shown; piped input falls back to `prompt_for_categories`.
+pub fn checklist_available() -> bool {
+    io::stdin().is_terminal() && io::stderr().is_terminal()
+}
+
+/// What the checklist offers once a category is picked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CategoryAction {
+    ChooseItems,
+    SelectAll,
+    SelectNone,
+}
+
+impl CategoryAction {
+    fn label(self) -> &'static str {
+        match self {
+            CategoryAction::ChooseItems => "Choose items...",
+            CategoryAction::SelectAll => "Select all",
+            CategoryAction::SelectNone => "Select none",
+        }
+    }
+}
+
+/// Per-item checklist state behind `prompt_for_items`. Nothing starts selected.
+pub struct ItemSelection {
+    categories: Vec<Category>,
+    items: Vec<Vec<CleanupItem>>,
+    selected: Vec<Vec<bool>>,
+    /// Whole-category choice for categories without items, such as Docker with nothing
+    /// reclaimable that can still be pruned.
+    chosen_empty: Vec<bool>,
+}
+
+impl ItemSelection {
+    /// Collects `categories` in order, each sorted by size descending. Categories without items
+    /// stay selectable as a whole.
+    pub fn new(report: &ScanReport, categories: &[Category]) -> Self {
+        let mut selection = Self {
+            categories: Vec::new(),
+            items: Vec::new(),
+            selected: Vec::new(),
+            chosen_empty: Vec::new(),
+        };
+        for category in categories {
+            let mut items = report
+                .report_for(*category)
+                .map(|category_report| category_report.items.clone())
+                .unwrap_or_default();
+            items.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+            selection.selected.push(vec![false; items.len()]);
+            selection.chosen_empty.push(false);
+            selection.items.push(items);
+            selection.categories.push(*category);
+        }
+        selection
+    }
+
+    fn is_chosen(&self, index: usize) -> bool {
+        if self.items[index].is_empty() {
+            self.chosen_empty[index]
+        } else {
+            self.selected[index].iter().any(|selected| *selected)
+        }
+    }
+
+    pub fn category_count(&self) -> usize {
+        self.categories.len()
+    }
+
+    pub fn nothing_selected(&self) -> bool {
+        !(0..self.category_count()).any(|index| self.is_chosen(index))
+    }
+
+    /// Docker is pruned as a whole, so its entries cannot be toggled one by one.
+    pub fn allows_item_choice(&self, index: usize) -> bool {
+        self.categories[index] != Category::Docker && !self.items[index].is_empty()
+    }
+
+    fn actions(&self, index: usize) -> Vec<CategoryAction> {
+        let mut actions = vec![CategoryAction::SelectAll, CategoryAction::SelectNone];
+        if self.allows_item_choice(index) {
+            actions.insert(0, CategoryAction::ChooseItems);
+        }
+        actions
+    }
+
+    pub fn category_label(&self, index: usize) -> String {
+        if self.items[index].is_empty() {
+            return format!(
+                "{:<10} {:>10} of {:>10}  {}",
+                self.categories[index].display_name(),
+                format_bytes(0),
+                format_bytes(0),
+                if self.chosen_empty[index] { "selected" } else { "not selected" }
+            );
+        }
+
+        let chosen = self.selected[index].iter().filter(|selected| **selected).count();
+        let chosen_size: u64 = self.items[index]
+            .iter()
+            .zip(&self.selected[index])
+            .filter(|(_, selected)| **selected)
+            .map(|(item, _)| item.size)
+            .sum();
+        format!(
+            "{:<10} {:>10} of {:>10}  {}/{} item(s) selected",
+            self.categories[index].display_name(),
+            format_bytes(chosen_size),
+            format_bytes(self.items[index].iter().map(|item| item.size).sum()),
+            chosen,
+            self.items[index].len()
+        )
+    }
+
+    pub fn item_labels(&self, index: usize, now: DateTime<Utc>) -> Vec<String> {
+        self.items[index]
+            .iter()
+            .map(|item| {
+                let age = item.age(now).map(format_age).unwrap_or_else(|| "-".to_string());
+                format!(
+                    "{:>10}  {:>4}  {}",
+                    format_bytes(item.size),
+                    age,
+                    display_path(item.path())
+                )
+            })
+            .collect()
+    }
+
+    pub fn selected_items(&self, index: usize) -> &[bool] {
+        &self.selected[index]
+    }
+
+    pub fn set_category(&mut self, index: usize, selected: bool) {
+        self.selected[index].fill(selected);
+        self.chosen_empty[index] = selected;
+    }
+
+    pub fn set_items(&mut self, index: usize, chosen: &[usize]) {
+        self.selected[index].fill(false);
+        for item in chosen {
+            if let Some(flag) = self.selected[index].get_mut(*item) {
+                *flag = true;
+            }
+        }
+    }
+
+    /// The chosen categories, and a report holding only the chosen items.
+    pub fn into_report(self) -> (Vec<Category>, ScanReport) {
+        let chosen_categories: Vec<bool> =
+            (0..self.category_count()).map(|index| self.is_chosen(index)).collect();
+        let mut categories = Vec::new();
+        let mut report = ScanReport::new();
+        for (((category, items), selected), chosen_category) in
+            self.categories.into_iter().zip(self.items).zip(self.selected).zip(chosen_categories)
+        {
+            if !chosen_category {
+                continue;
+            }
+            let chosen: Vec<_> = items
+                .into_iter()
+                .zip(selected)
+                .filter_map(|(item, selected)| selected.then_some(item))
+                .collect();
+            categories.push(category);
+            if !chosen.is_empty() {
+                report.add_items(category, chosen);
+            }
+        }
+        (categories, report)
+    }
+}
+
+/// Checklist UI: pick a category, then toggle its items or select all/none. Returns the chosen
+/// categories and a report with only the chosen items.
+pub fn prompt_for_items(
+    report: &ScanReport,
+    available_categories: &[Category],
+) -> Result<(Vec<Category>, ScanReport), AppError> {
+    let mut selection = ItemSelection::new(report, available_categories);
+    if selection.category_count() == 0 {
+        return Err(AppError::Cancelled);
+    }
+
+    let theme = ColorfulTheme::default();
+    let mut cursor = 0;
+    loop {
+        let mut entries: Vec<String> =
+            (0..selection.category_count()).map(|index| selection.category_label(index)).collect();
+        entries.push("Done: review the selection".to_string());
+
+        let Some(choice) = Select::with_theme(&theme)
+            .with_prompt("Choose a category to review (Esc cancels)")
+            .items(&entries)
+            .default(cursor)
+            .interact_opt()
+            .map_err(dialoguer_error)?
+        else {
+            return Err(AppError::Cancelled);
+        };
+
+        if choice == selection.category_count() {
+            if selection.nothing_selected() {
+                return Err(AppError::Cancelled);
+            }
+            return Ok(selection.into_report());
+        }
+        cursor = choice;
+
+        let actions = selection.actions(choice);
+        let labels: Vec<&str> = actions.iter().map(|action| action.label()).collect();
+        let Some(action) = Select::with_theme(&theme)
+            .with_prompt(entries[choice].trim_end())
+            .items(&labels)
+            .default(0)
+            .interact_opt()
+            .map_err(dialoguer_error)?
+        else {
+            continue;
+        };
+
+        match actions[action] {
+            CategoryAction::SelectAll => selection.set_category(choice, true),
+            CategoryAction::SelectNone => selection.set_category(choice, false),
+            CategoryAction::ChooseItems => {
+                let chosen = MultiSelect::with_theme(&theme)
+                    .with_prompt("Space toggles, 'a' toggles all, Enter confirms, Esc goes back")
+                    .items(&selection.item_labels(choice, Utc::now()))
+                    .defaults(selection.selected_items(choice))
+                    .max_length(20)
+                    .interact_opt()
+                    .map_err(dialoguer_error)?;
+                if let Some(chosen) = chosen {
+                    selection.set_items(choice, &chosen);
+                }
+            }
+        }
+    }
+}
+
+fn dialoguer_error(err: dialoguer::Error) -> AppError {
+    match err {
+        dialoguer::Error::IO(err) => AppError::Io(err),
+    }
+}
 
 pub fn prompt_for_categories(
     report: &ScanReport,
@@ -89,3 +342,87 @@ pub fn confirm_deletion(total_size: u64) -> Result<bool, AppError> {
     let answer = input.trim().to_ascii_lowercase();
     Ok(matches!(answer.as_str(), "y" | "yes"))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn report() -> ScanReport {
+        let mut report = ScanReport::new();
+        report.add_items(
+            Category::Rust,
+            vec![
+                CleanupItem::directory(Category::Rust, PathBuf::from("/src/small/target"), 10),
+                CleanupItem::directory(Category::Rust, PathBuf::from("/src/large/target"), 500),
+            ],
+        );
+        report.add_items(
+            Category::Nodejs,
+            vec![CleanupItem::directory(
+                Category::Nodejs,
+                PathBuf::from("/src/web/node_modules"),
+                7,
+            )],
+        );
+        report
+    }
+
+    #[test]
+    fn item_selection_sorts_by_size_and_keeps_only_chosen_items() {
+        let report = report();
+        let mut selection =
+            ItemSelection::new(&report, &[Category::Rust, Category::Python, Category::Nodejs]);
+
+        assert_eq!(selection.category_count(), 3);
+        assert!(selection.nothing_selected());
+        let labels = selection.item_labels(0, Utc::now());
+        assert!(labels[0].ends_with("/src/large/target"));
+        assert!(labels[1].ends_with("/src/small/target"));
+
+        selection.set_items(0, &[1]);
+        assert!(selection.category_label(0).contains("1/2 item(s) selected"));
+
+        let (categories, subset) = selection.into_report();
+        assert_eq!(categories, vec![Category::Rust]);
+        let items = &subset.report_for(Category::Rust).expect("rust is selected").items;
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].path, PathBuf::from("/src/small/target"));
+    }
+
+    #[test]
+    fn item_selection_toggles_whole_categories() {
+        let report = report();
+        let mut selection = ItemSelection::new(&report, &[Category::Rust, Category::Nodejs]);
+
+        selection.set_category(0, true);
+        selection.set_category(1, true);
+        selection.set_category(0, false);
+
+        let (categories, subset) = selection.into_report();
+        assert_eq!(categories, vec![Category::Nodejs]);
+        assert_eq!(subset.total_size(), 7);
+    }
+
+    #[test]
+    fn item_selection_keeps_categories_without_items_selectable() {
+        let report = report();
+        let mut selection = ItemSelection::new(&report, &[Category::Rust, Category::Docker]);
+
+        assert_eq!(selection.category_count(), 2);
+        assert_eq!(selection.actions(0)[0], CategoryAction::ChooseItems);
+        assert_eq!(
+            selection.actions(1),
+            vec![CategoryAction::SelectAll, CategoryAction::SelectNone]
+        );
+        assert!(selection.category_label(1).ends_with("not selected"));
+
+        selection.set_category(1, true);
+        assert!(!selection.nothing_selected());
+
+        let (categories, subset) = selection.into_report();
+        assert_eq!(categories, vec![Category::Docker]);
+        assert_eq!(subset.total_size(), 0);
+    }
+}
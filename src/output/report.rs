@@ -3,9 +3,14 @@ use std::path::{Path, PathBuf};
 
 use dirs_next as dirs;
 
+use crate::app::plan::DriftedItem;
+use crate::state::journal::JournalRecord;
+use crate::state::quarantine::QuarantineEntry;
 use crate::targets::category::Category;
+use crate::targets::item::CleanupItem;
 use crate::targets::report::ScanReport;
 
+use super::age::format_age;
 use super::bytes::format_bytes;
 
 pub fn display_path(path: &Path) -> String {
@@ -31,7 +36,7 @@ pub fn print_scan_report(report: &ScanReport, categories: &[Category], verbose:
             );
             if verbose {
                 for item in &category_report.items {
-                    println!("    • {:<60} {}", display_path(item.path()), format_bytes(item.size));
+                    print_item_details(item);
                 }
             }
         }
@@ -39,6 +44,14 @@ pub fn print_scan_report(report: &ScanReport, categories: &[Category], verbose:
     println!("Total reclaimable: {}", format_bytes(report.total_size()));
 }
 
+fn print_item_details(item: &CleanupItem) {
+    let age = item
+        .age(chrono::Utc::now())
+        .map(|age| format!("  {} old", format_age(age)))
+        .unwrap_or_default();
+    println!("    • {:<60} {:>10}{}", display_path(item.path()), format_bytes(item.size), age);
+}
+
 pub fn print_list_results(results: &BTreeMap<Category, Vec<String>>) {
     println!("Found cleanup targets:");
     for (category, targets) in results {
@@ -64,7 +77,7 @@ pub fn print_deletion_plan(report: &ScanReport, categories: &[Category], verbose
             );
             for item in &category_report.items {
                 if verbose {
-                    println!("    • {:<60} {}", display_path(item.path()), format_bytes(item.size));
+                    print_item_details(item);
                 } else {
                     println!("    • {}", display_path(item.path()));
                 }
@@ -73,3 +86,69 @@ pub fn print_deletion_plan(report: &ScanReport, categories: &[Category], verbose
     }
     println!("Total to delete: {}", format_bytes(report.total_size()));
 }
+
+pub fn print_quarantine_entries(entries: &[QuarantineEntry]) {
+    if entries.is_empty() {
+        println!("Quarantine is empty.");
+        return;
+    }
+
+    println!("Quarantined items:");
+    for entry in entries {
+        println!(
+            "- {:<18} {:<8} {:>10}  {}  {}",
+            entry.id,
+            entry.category.display_name(),
+            format_bytes(entry.size),
+            entry.quarantined_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            display_path(&entry.original_path)
+        );
+    }
+}
+
+pub fn print_history(records: &[JournalRecord]) {
+    if records.is_empty() {
+        println!("No deletions recorded.");
+        return;
+    }
+
+    println!("Deletion history:");
+    for record in records {
+        let size =
+            if record.succeeded() { format_bytes(record.size) } else { "FAILED".to_string() };
+        println!(
+            "- {}  {:<10} {:<8} {:>10}  {}",
+            record.timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            record.action.as_str(),
+            record.category.display_name(),
+            size,
+            display_path(&record.path)
+        );
+        if let Some(error) = &record.error {
+            println!("    {error}");
+        }
+    }
+
+    let succeeded: Vec<_> = records.iter().filter(|record| record.succeeded()).collect();
+    let failed = records.len() - succeeded.len();
+    print!(
+        "Total: {} across {} record(s)",
+        format_bytes(succeeded.iter().map(|record| record.size).sum()),
+        succeeded.len()
+    );
+    if failed > 0 {
+        print!(", {failed} failed");
+    }
+    println!();
+}
+
+pub fn print_plan_drift(drifted: &[DriftedItem]) {
+    if drifted.is_empty() {
+        return;
+    }
+
+    println!("Skipped {} item(s) that changed since the plan was saved:", drifted.len());
+    for drift in drifted {
+        println!("    • {} ({})", display_path(drift.item.path()), drift.reason);
+    }
+}
@@ -0,0 +1,187 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::fs::remove::RemovalMode;
+use crate::targets::category::Category;
+use crate::targets::item::CleanupItem;
+
+use super::state_dir;
+
+const JOURNAL_FILE: &str = "journal.jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalAction {
+    Delete,
+    Trash,
+    Quarantine,
+    Purge,
+    Prune,
+}
+
+impl JournalAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JournalAction::Delete => "delete",
+            JournalAction::Trash => "trash",
+            JournalAction::Quarantine => "quarantine",
+            JournalAction::Purge => "purge",
+            JournalAction::Prune => "prune",
+        }
+    }
+}
+
+impl From<RemovalMode> for JournalAction {
+    fn from(mode: RemovalMode) -> Self {
+        match mode {
+            RemovalMode::Delete => JournalAction::Delete,
+            RemovalMode::Trash => JournalAction::Trash,
+            RemovalMode::Quarantine => JournalAction::Quarantine,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalOutcome {
+    Success,
+    Failure,
+}
+
+/// One line of the deletion journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalRecord {
+    pub timestamp: DateTime<Utc>,
+    pub action: JournalAction,
+    pub category: Category,
+    pub path: PathBuf,
+    pub size: u64,
+    pub outcome: JournalOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl JournalRecord {
+    pub fn new(action: JournalAction, item: &CleanupItem, result: Result<(), &AppError>) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            action,
+            category: item.category,
+            path: item.path.clone(),
+            size: item.size,
+            outcome: if result.is_ok() { JournalOutcome::Success } else { JournalOutcome::Failure },
+            error: result.err().map(ToString::to_string),
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.outcome == JournalOutcome::Success
+    }
+}
+
+/// Append-only JSONL log of every removal prf performed.
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub fn open() -> Result<Self, AppError> {
+        Ok(Self::at(state_dir()?.join(JOURNAL_FILE)))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn append(&self, records: &[JournalRecord]) -> Result<(), AppError> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut buffer = Vec::new();
+        for record in records {
+            serde_json::to_writer(&mut buffer, record)?;
+            buffer.push(b'\n');
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        file.write_all(&buffer)?;
+        Ok(())
+    }
+
+    /// All records in the order they were written. Malformed lines are skipped.
+    pub fn records(&self) -> Result<Vec<JournalRecord>, AppError> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(AppError::Io(err)),
+        };
+
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if let Ok(record) = serde_json::from_str::<JournalRecord>(&line) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// Appends `records`, reporting (but not propagating) journal failures so a completed
+/// deletion is never reported as failed because its log could not be written.
+pub fn record(records: &[JournalRecord]) {
+    let result = Journal::open().and_then(|journal| journal.append(records));
+    if let Err(err) = result {
+        eprintln!("Warning: failed to write deletion journal: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn append_and_read_round_trip() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let journal = Journal::at(temp.path().join("state/journal.jsonl"));
+        let item = CleanupItem::directory(Category::Rust, PathBuf::from("/work/app/target"), 42);
+
+        journal
+            .append(&[JournalRecord::new(JournalAction::Delete, &item, Ok(()))])
+            .expect("first append succeeds");
+        let failure = AppError::Cancelled;
+        journal
+            .append(&[JournalRecord::new(JournalAction::Trash, &item, Err(&failure))])
+            .expect("second append succeeds");
+
+        let records = journal.records().expect("records are readable");
+        assert_eq!(records.len(), 2);
+        assert!(records[0].succeeded());
+        assert_eq!(records[0].size, 42);
+        assert_eq!(records[1].action, JournalAction::Trash);
+        assert_eq!(records[1].error.as_deref(), Some("Operation cancelled by user"));
+    }
+
+    #[test]
+    fn records_skip_malformed_lines() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let file = temp.child("journal.jsonl");
+        file.write_str("not json\n").expect("journal exists");
+
+        let journal = Journal::at(file.path().to_path_buf());
+        assert!(journal.records().expect("records are readable").is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use crate::error::AppError;
+
+pub mod journal;
+pub mod quarantine;
+
+/// Directory holding prf-managed state (quarantine, journal).
+///
+/// Resolution order: `PRF_STATE_DIR`, `$XDG_STATE_HOME/prf`, then `~/.local/state/prf`.
+pub fn state_dir() -> Result<PathBuf, AppError> {
+    if let Some(dir) = non_empty_env("PRF_STATE_DIR") {
+        return Ok(dir);
+    }
+    if let Some(dir) = non_empty_env("XDG_STATE_HOME") {
+        return Ok(dir.join("prf"));
+    }
+
+    let home = std::env::var_os("HOME").map(PathBuf::from).ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "HOME is not set; cannot locate state")
+    })?;
+    Ok(home.join(".local/state/prf"))
+}
+
+fn non_empty_env(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key).filter(|value| !value.is_empty()).map(PathBuf::from)
+}
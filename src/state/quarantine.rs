@@ -0,0 +1,234 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::AppError;
+use crate::fs::relocate::move_path;
+use crate::fs::remove::remove_item;
+use crate::targets::category::Category;
+use crate::targets::item::{CleanupItem, ItemKind};
+
+use super::state_dir;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const PAYLOAD_NAME: &str = "payload";
+
+/// Manifest stored next to each quarantined payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    pub id: String,
+    pub original_path: PathBuf,
+    pub category: Category,
+    pub size: u64,
+    pub kind: ItemKind,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+impl QuarantineEntry {
+    /// The cleanup item this entry was created from, at its original location.
+    pub fn to_item(&self) -> CleanupItem {
+        CleanupItem {
+            category: self.category,
+            path: self.original_path.clone(),
+            size: self.size,
+            kind: self.kind,
+            modified: None,
+            project: None,
+        }
+    }
+}
+
+/// prf-managed staging area. Each entry is `<root>/<id>/{manifest.json,payload}`.
+pub struct Quarantine {
+    root: PathBuf,
+}
+
+impl Quarantine {
+    pub fn open() -> Result<Self, AppError> {
+        Ok(Self::at(state_dir()?.join("quarantine")))
+    }
+
+    pub fn at(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn entry_dir(&self, id: &str) -> PathBuf {
+        self.root.join(id)
+    }
+
+    fn payload_path(&self, entry: &QuarantineEntry) -> PathBuf {
+        self.entry_dir(&entry.id).join(PAYLOAD_NAME)
+    }
+
+    /// Moves `item` into the quarantine. Returns `None` when the item has already vanished.
+    pub fn stash(
+        &self,
+        item: &CleanupItem,
+        verbose: bool,
+    ) -> Result<Option<QuarantineEntry>, AppError> {
+        match fs::symlink_metadata(&item.path) {
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(AppError::Io(err)),
+        }
+
+        fs::create_dir_all(&self.root)?;
+        let now = Utc::now();
+        let id = self.reserve_id(&now)?;
+        let entry = QuarantineEntry {
+            id,
+            original_path: std::path::absolute(&item.path)?,
+            category: item.category,
+            size: item.size,
+            kind: item.kind,
+            quarantined_at: now,
+        };
+
+        // The manifest is written before the move so a payload is never left without one.
+        let entry_dir = self.entry_dir(&entry.id);
+        fs::write(entry_dir.join(MANIFEST_FILE), serde_json::to_vec_pretty(&entry)?)?;
+        if let Err(err) = move_path(&item.path, &self.payload_path(&entry), verbose) {
+            let _ = fs::remove_dir_all(&entry_dir);
+            return Err(err);
+        }
+
+        if verbose {
+            println!("Quarantined {} as {}", item.path.display(), entry.id);
+        }
+        Ok(Some(entry))
+    }
+
+    fn reserve_id(&self, now: &DateTime<Utc>) -> Result<String, AppError> {
+        let stamp = now.format("%Y%m%d-%H%M%S");
+        let mut sequence = 1;
+        loop {
+            let id = format!("{stamp}-{sequence}");
+            match fs::create_dir(self.entry_dir(&id)) {
+                Ok(()) => return Ok(id),
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => sequence += 1,
+                Err(err) => return Err(AppError::Io(err)),
+            }
+        }
+    }
+
+    /// All entries, oldest first. Directories without a readable manifest are ignored.
+    pub fn entries(&self) -> Result<Vec<QuarantineEntry>, AppError> {
+        let read_dir = match fs::read_dir(&self.root) {
+            Ok(read_dir) => read_dir,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(AppError::Io(err)),
+        };
+
+        let mut entries = Vec::new();
+        for dir_entry in read_dir {
+            let manifest = dir_entry?.path().join(MANIFEST_FILE);
+            let Ok(contents) = fs::read(&manifest) else {
+                continue;
+            };
+            if let Ok(entry) = serde_json::from_slice::<QuarantineEntry>(&contents) {
+                entries.push(entry);
+            }
+        }
+
+        entries.sort_by(|a, b| a.quarantined_at.cmp(&b.quarantined_at).then(a.id.cmp(&b.id)));
+        Ok(entries)
+    }
+
+    /// Entries whose id equals `query` or whose original path equals `query`.
+    pub fn find(&self, query: &str) -> Result<Vec<QuarantineEntry>, AppError> {
+        let query_path = std::path::absolute(Path::new(query))?;
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.id == query || entry.original_path == query_path)
+            .collect())
+    }
+
+    /// Moves the payload back to its original location and drops the entry.
+    pub fn restore(&self, entry: &QuarantineEntry, verbose: bool) -> Result<(), AppError> {
+        if fs::symlink_metadata(&entry.original_path).is_ok() {
+            return Err(AppError::RestoreTargetExists(entry.original_path.display().to_string()));
+        }
+
+        if let Some(parent) = entry.original_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        move_path(&self.payload_path(entry), &entry.original_path, verbose)?;
+        fs::remove_dir_all(self.entry_dir(&entry.id))?;
+        Ok(())
+    }
+
+    /// Permanently deletes the payload and its manifest.
+    pub fn purge(&self, entry: &QuarantineEntry, verbose: bool) -> Result<(), AppError> {
+        remove_item(&self.payload_path(entry), entry.kind, verbose)?;
+        remove_item(&self.entry_dir(&entry.id), ItemKind::Directory, verbose)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn stash_and_restore_round_trip() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let project = temp.child("project/node_modules");
+        project.child("index.js").write_str("console.log('cache');").expect("file exists");
+
+        let quarantine = Quarantine::at(temp.path().join("quarantine"));
+        let item = CleanupItem::directory(Category::Nodejs, project.path().to_path_buf(), 21);
+        let entry = quarantine.stash(&item, false).expect("stash succeeds").expect("item exists");
+
+        project.assert(predicates::path::missing());
+        let entries = quarantine.entries().expect("entries are readable");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original_path, project.path());
+        assert_eq!(entries[0].category, Category::Nodejs);
+        assert_eq!(entries[0].size, 21);
+
+        let found = quarantine.find(&entry.id).expect("lookup succeeds");
+        assert_eq!(found.len(), 1);
+        quarantine.restore(&found[0], false).expect("restore succeeds");
+
+        project.child("index.js").assert("console.log('cache');");
+        assert!(quarantine.entries().expect("entries are readable").is_empty());
+    }
+
+    #[test]
+    fn restore_refuses_to_overwrite_existing_path() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let file = temp.child("cache.log");
+        file.write_str("old").expect("file exists");
+
+        let quarantine = Quarantine::at(temp.path().join("quarantine"));
+        let item = CleanupItem::file(Category::Python, file.path().to_path_buf(), 3);
+        quarantine.stash(&item, false).expect("stash succeeds");
+        file.write_str("new").expect("file is recreated");
+
+        let entry = quarantine.find(&file.path().to_string_lossy()).expect("lookup succeeds");
+        let result = quarantine.restore(&entry[0], false);
+        assert!(matches!(result, Err(AppError::RestoreTargetExists(_))));
+        file.assert("new");
+    }
+
+    #[test]
+    fn purge_removes_payload_and_manifest() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let dir = temp.child(".venv");
+        dir.child("bin/python").write_str("#!").expect("file exists");
+
+        let quarantine = Quarantine::at(temp.path().join("quarantine"));
+        let item = CleanupItem::directory(Category::Python, dir.path().to_path_buf(), 2);
+        let entry = quarantine.stash(&item, false).expect("stash succeeds").expect("item exists");
+
+        quarantine.purge(&entry, false).expect("purge succeeds");
+        assert!(quarantine.entries().expect("entries are readable").is_empty());
+        temp.child("quarantine").child(&entry.id).assert(predicates::path::missing());
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use dirs_next as dirs;
+
+use crate::error::AppError;
+
+use super::category::Category;
+use super::item::CleanupItem;
+use super::project::{self, ProjectRule, RuleTable};
+use super::target::{CleanupTarget, ScanScope};
+
+/// An Android module is a directory with a main manifest; its outputs belong to `android`, not
+/// to the plain Gradle rules in `jvm`.
+pub(crate) const ANDROID_MODULE_MARKERS: &[&str] = &["src/main/AndroidManifest.xml"];
+
+const ANDROID_RULES: &[ProjectRule] = &[
+    ProjectRule::new("build", ANDROID_MODULE_MARKERS),
+    ProjectRule::new(".cxx", ANDROID_MODULE_MARKERS),
+    ProjectRule::new(".externalNativeBuild", ANDROID_MODULE_MARKERS),
+];
+
+const GRADLE_FILES: &[&str] = &["build.gradle", "build.gradle.kts"];
+const API_LEVEL_KEYS: &[&str] =
+    &["compileSdk", "compileSdkVersion", "targetSdk", "targetSdkVersion", "apiLevel"];
+
+/// SDK versions that projects under the scan roots or existing emulators still need.
+#[derive(Debug, Default)]
+struct SdkReferences {
+    build_tools: HashSet<String>,
+    api_levels: HashSet<u32>,
+}
+
+impl SdkReferences {
+    fn read_gradle_file(&mut self, contents: &str) {
+        for line in contents.lines().map(str::trim) {
+            if let Some(rest) = keyword_value(line, "buildToolsVersion")
+                && let Some(version) = rest.split(['"', '\'']).nth(1)
+            {
+                self.build_tools.insert(version.to_string());
+            }
+            for key in API_LEVEL_KEYS {
+                if let Some(level) = keyword_value(line, key).and_then(leading_number) {
+                    self.api_levels.insert(level);
+                }
+            }
+        }
+    }
+
+    /// Records the system image an AVD boots from (`image.sysdir.1=system-images/android-34/…`).
+    fn read_avd_config(&mut self, contents: &str) {
+        for line in contents.lines() {
+            if let Some((key, value)) = line.split_once('=')
+                && key.trim().starts_with("image.sysdir")
+                && let Some(level) = value
+                    .trim()
+                    .split('/')
+                    .find_map(|component| component.strip_prefix("android-"))
+                    .and_then(leading_number)
+            {
+                self.api_levels.insert(level);
+            }
+        }
+    }
+}
+
+/// The text after `key` when `line` assigns or calls it, e.g. `compileSdk = 34`.
+fn keyword_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(key)?;
+    rest.starts_with([' ', '\t', '=', '(']).then_some(rest)
+}
+
+fn leading_number(text: &str) -> Option<u32> {
+    let digits: String = text
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Numeric components of a build-tools version such as `34.0.0` or `35.0.0-rc1`.
+fn version_key(name: &str) -> Option<Vec<u64>> {
+    let release = name.split('-').next()?;
+    release.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// SDK packages no reference needs. The newest build-tools version and system image are always
+/// kept, as are directories whose version cannot be parsed.
+fn outdated_sdk_packages(sdk: &Path, references: &SdkReferences) -> Vec<PathBuf> {
+    let mut outdated = Vec::new();
+
+    let build_tools = versioned_dirs(&sdk.join("build-tools"), version_key);
+    let newest_tools = build_tools.iter().map(|(_, key)| key).max().cloned();
+    for (path, key) in &build_tools {
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned());
+        let referenced = name.is_some_and(|name| references.build_tools.contains(&name));
+        if !referenced && Some(key) != newest_tools.as_ref() {
+            outdated.push(path.clone());
+        }
+    }
+
+    let images = versioned_dirs(&sdk.join("system-images"), |name| {
+        name.strip_prefix("android-").and_then(leading_number)
+    });
+    let newest_image = images.iter().map(|(_, level)| *level).max();
+    for (path, level) in &images {
+        if !references.api_levels.contains(level) && Some(*level) != newest_image {
+            outdated.push(path.clone());
+        }
+    }
+
+    outdated.sort();
+    outdated
+}
+
+fn versioned_dirs<K>(parent: &Path, key: impl Fn(&str) -> Option<K>) -> Vec<(PathBuf, K)> {
+    let Ok(entries) = fs::read_dir(parent) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter_map(|entry| {
+            let key = key(&entry.file_name().to_string_lossy())?;
+            Some((entry.path(), key))
+        })
+        .collect()
+}
+
+pub struct AndroidTarget {
+    rules: RuleTable<ProjectRule>,
+    /// The scope whose projects protect SDK packages when a saved plan is re-checked.
+    scope: ScanScope,
+}
+
+impl AndroidTarget {
+    pub fn new(scope: ScanScope) -> Self {
+        Self { rules: RuleTable::new(ANDROID_RULES), scope }
+    }
+
+    /// `ANDROID_HOME`, then the deprecated `ANDROID_SDK_ROOT`, then Android Studio's default
+    /// install location.
+    fn sdk_root() -> Option<PathBuf> {
+        let from_env = ["ANDROID_HOME", "ANDROID_SDK_ROOT"].into_iter().find_map(project::env_dir);
+        from_env.or_else(|| {
+            let home = dirs::home_dir()?;
+            [home.join("Library/Android/sdk"), home.join("Android/Sdk")]
+                .into_iter()
+                .find(|path| path.is_dir())
+        })
+    }
+
+    fn user_home() -> Option<PathBuf> {
+        project::env_dir("ANDROID_USER_HOME")
+            .or_else(|| dirs::home_dir().map(|home| home.join(".android")))
+    }
+
+    fn avd_home() -> Option<PathBuf> {
+        project::env_dir("ANDROID_AVD_HOME")
+            .or_else(|| Self::user_home().map(|user_home| user_home.join("avd")))
+    }
+
+    fn avd_dirs() -> Vec<PathBuf> {
+        let Some(avd_home) = Self::avd_home() else {
+            return Vec::new();
+        };
+        versioned_dirs(&avd_home, |name| name.ends_with(".avd").then_some(()))
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect()
+    }
+
+    /// Build caches under `~/.android` and the snapshots of every emulator.
+    fn global_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        if let Some(user_home) = Self::user_home() {
+            paths.push(user_home.join("cache"));
+            paths.push(user_home.join("build-cache"));
+        }
+        paths.extend(Self::avd_dirs().into_iter().map(|avd| avd.join("snapshots")));
+        paths
+    }
+
+    fn references(scope: &ScanScope) -> SdkReferences {
+        let mut references = SdkReferences::default();
+
+        for root in scope.roots() {
+            if !root.exists() {
+                continue;
+            }
+
+            for entry in scope.walk(root).flatten() {
+                if entry.file_type().is_file()
+                    && GRADLE_FILES.iter().any(|name| entry.file_name() == *name)
+                    && let Ok(contents) = fs::read_to_string(entry.path())
+                {
+                    references.read_gradle_file(&contents);
+                }
+            }
+        }
+
+        for avd in Self::avd_dirs() {
+            if let Ok(contents) = fs::read_to_string(avd.join("config.ini")) {
+                references.read_avd_config(&contents);
+            }
+        }
+
+        references
+    }
+
+    fn unused_sdk_packages(scope: &ScanScope) -> Vec<PathBuf> {
+        Self::sdk_root()
+            .map(|sdk| outdated_sdk_packages(&sdk, &Self::references(scope)))
+            .unwrap_or_default()
+    }
+}
+
+impl CleanupTarget for AndroidTarget {
+    fn category(&self) -> Category {
+        Category::Android
+    }
+
+    fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
+        let artifacts = project::discover_artifacts(scope, &self.rules);
+        let mut items = project::artifact_items(Category::Android, artifacts);
+        if !self.scope.current() {
+            items.extend(project::global_items(Category::Android, &Self::global_paths()));
+            items.extend(project::global_items(
+                Category::Android,
+                &Self::unused_sdk_packages(scope),
+            ));
+        }
+        Ok(items)
+    }
+
+    fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
+        let mut targets = project::list_artifacts(&project::discover_artifacts(scope, &self.rules));
+        if !self.scope.current() {
+            targets.extend(project::list_global(&Self::global_paths()));
+            targets.extend(
+                Self::unused_sdk_packages(scope)
+                    .iter()
+                    .map(|path| format!("{} (not referenced)", path.display())),
+            );
+        }
+        Ok(targets)
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        self.rules.matches(path)
+            || Self::global_paths().iter().any(|candidate| candidate == path)
+            || Self::unused_sdk_packages(&self.scope).iter().any(|candidate| candidate == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+
+    #[test]
+    fn discover_reports_module_outputs_only_in_android_modules() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("app/settings.gradle").write_str("").expect("marker exists");
+        temp.child("app/app/build.gradle").write_str("android {}").expect("marker exists");
+        temp.child("app/app/src/main/AndroidManifest.xml").write_str("<manifest/>").expect("ok");
+        temp.child("app/app/build/outputs/apk").create_dir_all().expect("artifact exists");
+        temp.child("app/app/.cxx/Debug").create_dir_all().expect("artifact exists");
+        temp.child("server/build.gradle").write_str("").expect("marker exists");
+        temp.child("server/build/libs").create_dir_all().expect("artifact exists");
+
+        let target =
+            AndroidTarget::new(ScanScope::new(vec![temp.path().to_path_buf()], true, false));
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![temp.path().join("app/app/.cxx"), temp.path().join("app/app/build")]
+        );
+        assert!(!target.owns(&temp.path().join("server/build")));
+    }
+
+    #[test]
+    fn outdated_sdk_packages_keep_referenced_and_newest_versions() {
+        let temp = TempDir::new().expect("temp directory is created");
+        for dir in [
+            "build-tools/30.0.3",
+            "build-tools/33.0.2",
+            "build-tools/34.0.0",
+            "build-tools/35.0.0-rc1",
+            "system-images/android-30",
+            "system-images/android-31",
+            "system-images/android-34",
+            "system-images/android-VanillaIceCream",
+        ] {
+            temp.child(dir).create_dir_all().expect("package exists");
+        }
+
+        let mut references = SdkReferences::default();
+        references.read_gradle_file(
+            "android {\n    compileSdkVersion 31\n    buildToolsVersion \"33.0.2\"\n}\n",
+        );
+        references.read_avd_config("image.sysdir.1=system-images/android-30/google_apis/x86_64/\n");
+
+        assert_eq!(
+            outdated_sdk_packages(temp.path(), &references),
+            vec![temp.path().join("build-tools/30.0.3"), temp.path().join("build-tools/34.0.0")]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use dirs_next as dirs;
 
@@ -55,4 +55,8 @@ impl CleanupTarget for BrewTarget {
         }
         Ok(targets)
     }
+
+    fn owns(&self, path: &Path) -> bool {
+        Self::brew_paths().iter().any(|candidate| candidate == path)
+    }
 }
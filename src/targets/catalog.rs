@@ -1,23 +1,60 @@
+use std::sync::OnceLock;
+
+use super::android::AndroidTarget;
 use super::brew::BrewTarget;
 use super::category::Category;
+use super::custom::{self, CustomTarget};
+use super::dart;
 use super::docker::DockerTarget;
+use super::dotnet;
+use super::elixir;
+use super::go;
+use super::haskell;
+use super::iac;
+use super::jvm;
+use super::native::NativeTarget;
 use super::nodejs::NodejsTarget;
+use super::php;
 use super::python::PythonTarget;
+use super::ruby;
+use super::runtimes::RuntimesTarget;
 use super::rust::RustTarget;
-use super::target::CleanupTarget;
+use super::rustup::RustupTarget;
+use super::target::{CleanupTarget, ScanScope};
 use super::xcode::XcodeTarget;
 
-const CATEGORY_ORDER: [Category; 6] = [
+/// Built-in categories; custom categories from the config file follow in declaration order.
+/// `rustup` and `runtimes` remove installed toolchains and runtime versions rather than caches, so
+/// they are left out and run only when requested by name.
+const CATEGORY_ORDER: [Category; 17] = [
     Category::Xcode,
     Category::Python,
     Category::Rust,
     Category::Nodejs,
+    Category::Go,
+    Category::Jvm,
+    Category::Android,
+    Category::Dart,
+    Category::Dotnet,
+    Category::Ruby,
+    Category::Php,
+    Category::Elixir,
+    Category::Haskell,
+    Category::Native,
+    Category::Iac,
     Category::Brew,
     Category::Docker,
 ];
 
 pub fn category_order() -> &'static [Category] {
-    &CATEGORY_ORDER
+    static ORDER: OnceLock<Vec<Category>> = OnceLock::new();
+    ORDER.get_or_init(|| {
+        CATEGORY_ORDER
+            .iter()
+            .copied()
+            .chain(custom::definitions().iter().map(|definition| definition.category()))
+            .collect()
+    })
 }
 
 pub fn categories_for_mode(current: bool) -> Vec<Category> {
@@ -42,13 +79,21 @@ pub fn unique_categories(categories: Vec<Category>) -> Vec<Category> {
     unique
 }
 
+/// Resolves the requested categories. Without `--type` or `--all`, `defaults` (from the config
+/// file) are used when non-empty, silently dropping those unavailable in `--current` mode.
 pub fn resolve(
     categories: &[Category],
     all: bool,
     current: bool,
+    defaults: &[Category],
 ) -> Result<Vec<Category>, crate::error::AppError> {
-    let resolved = if all || categories.is_empty() {
+    let resolved = if all || (categories.is_empty() && defaults.is_empty()) {
         categories_for_mode(current)
+    } else if categories.is_empty() {
+        unique_categories(defaults.to_vec())
+            .into_iter()
+            .filter(|category| category.supports_current_mode(current))
+            .collect()
     } else {
         unique_categories(categories.to_vec())
     };
@@ -65,7 +110,8 @@ pub fn resolve(
     Ok(resolved)
 }
 
-pub fn build_targets(categories: &[Category], current: bool) -> Vec<Box<dyn CleanupTarget>> {
+pub fn build_targets(categories: &[Category], scope: &ScanScope) -> Vec<Box<dyn CleanupTarget>> {
+    let current = scope.current();
     let mut targets: Vec<Box<dyn CleanupTarget>> = Vec::new();
 
     for category in categories {
@@ -75,11 +121,29 @@ pub fn build_targets(categories: &[Category], current: bool) -> Vec<Box<dyn Clea
 
         match category {
             Category::Xcode => targets.push(Box::new(XcodeTarget::new(current))),
-            Category::Python => targets.push(Box::new(PythonTarget::new())),
-            Category::Rust => targets.push(Box::new(RustTarget::new())),
-            Category::Nodejs => targets.push(Box::new(NodejsTarget::new())),
+            Category::Python => targets.push(Box::new(PythonTarget::new(current))),
+            Category::Rust => targets.push(Box::new(RustTarget::new(current))),
+            Category::Rustup => targets.push(Box::new(RustupTarget::new(scope.clone()))),
+            Category::Nodejs => targets.push(Box::new(NodejsTarget::new(current))),
+            Category::Go => targets.push(Box::new(go::target(current))),
+            Category::Jvm => targets.push(Box::new(jvm::target(current))),
+            Category::Android => targets.push(Box::new(AndroidTarget::new(scope.clone()))),
+            Category::Dart => targets.push(Box::new(dart::target(current))),
+            Category::Dotnet => targets.push(Box::new(dotnet::target(current))),
+            Category::Ruby => targets.push(Box::new(ruby::target(current))),
+            Category::Php => targets.push(Box::new(php::target(current))),
+            Category::Elixir => targets.push(Box::new(elixir::target(current))),
+            Category::Haskell => targets.push(Box::new(haskell::target(current))),
+            Category::Native => targets.push(Box::new(NativeTarget::new(current))),
+            Category::Iac => targets.push(Box::new(iac::target(current))),
+            Category::Runtimes => targets.push(Box::new(RuntimesTarget::new(scope.clone()))),
             Category::Brew => targets.push(Box::new(BrewTarget::new())),
             Category::Docker => targets.push(Box::new(DockerTarget::new())),
+            Category::Custom(name) => {
+                if let Some(definition) = custom::definition(name) {
+                    targets.push(Box::new(CustomTarget::new(definition)));
+                }
+            }
         }
     }
 
@@ -93,6 +157,7 @@ mod tests {
     #[test]
     fn categories_for_current_mode_excludes_system_targets() {
         let categories = categories_for_mode(true);
+        assert!(!categories.contains(&Category::Runtimes));
         assert!(!categories.contains(&Category::Brew));
         assert!(!categories.contains(&Category::Docker));
     }
@@ -103,11 +168,34 @@ mod tests {
         assert_eq!(categories, CATEGORY_ORDER);
     }
 
+    #[test]
+    fn resolve_uses_config_defaults_only_without_explicit_selection() {
+        let defaults = [Category::Rust, Category::Docker];
+
+        let resolved = resolve(&[], false, false, &defaults).expect("defaults resolve");
+        assert_eq!(resolved, defaults);
+
+        let current = resolve(&[], false, true, &defaults).expect("defaults resolve");
+        assert_eq!(current, vec![Category::Rust]);
+
+        let explicit = resolve(&[Category::Python], false, false, &defaults).expect("resolves");
+        assert_eq!(explicit, vec![Category::Python]);
+
+        let all = resolve(&[], true, false, &defaults).expect("all resolves");
+        assert_eq!(all, CATEGORY_ORDER);
+        assert!(!all.contains(&Category::Rustup));
+        assert!(!all.contains(&Category::Runtimes));
+
+        let opt_in = resolve(&[Category::Rustup], false, false, &defaults).expect("resolves");
+        assert_eq!(opt_in, vec![Category::Rustup]);
+        assert!(resolve(&[Category::Rustup], false, true, &defaults).is_err());
+    }
+
     #[test]
     fn build_targets_excludes_brew_and_docker_in_current_mode() {
         let requested = vec![Category::Xcode, Category::Brew, Category::Docker, Category::Python];
 
-        let targets = build_targets(&requested, true);
+        let targets = build_targets(&requested, &ScanScope::new(Vec::new(), true, false));
         let target_categories: Vec<Category> =
             targets.iter().map(|target| target.category()).collect();
 
@@ -117,7 +205,7 @@ mod tests {
 
     #[test]
     fn build_targets_include_requested_categories_when_not_current_mode() {
-        let targets = build_targets(&CATEGORY_ORDER, false);
+        let targets = build_targets(&CATEGORY_ORDER, &ScanScope::new(Vec::new(), false, false));
         let target_categories: Vec<Category> =
             targets.iter().map(|target| target.category()).collect();
         assert_eq!(target_categories, CATEGORY_ORDER);
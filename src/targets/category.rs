@@ -1,22 +1,60 @@
 use std::fmt;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::custom::{self, CustomScope};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Category {
     Xcode,
     Python,
     Rust,
+    Rustup,
     Nodejs,
+    Go,
+    Jvm,
+    Android,
+    Dart,
+    Dotnet,
+    Ruby,
+    Php,
+    Elixir,
+    Haskell,
+    Native,
+    Iac,
+    Runtimes,
     Brew,
     Docker,
+    /// A category declared in the config file, identified by its name.
+    Custom(&'static str),
 }
 
 impl Category {
     pub fn from_name(value: &str) -> Option<Self> {
-        match value.to_ascii_lowercase().as_str() {
+        let value = value.to_ascii_lowercase();
+        Self::builtin(&value).or_else(|| custom::definition(&value).map(|def| def.category()))
+    }
+
+    pub(crate) fn builtin(value: &str) -> Option<Self> {
+        match value {
             "xcode" => Some(Category::Xcode),
             "python" => Some(Category::Python),
             "rust" => Some(Category::Rust),
+            "rustup" => Some(Category::Rustup),
             "nodejs" => Some(Category::Nodejs),
+            "go" => Some(Category::Go),
+            "jvm" => Some(Category::Jvm),
+            "android" => Some(Category::Android),
+            "dart" => Some(Category::Dart),
+            "dotnet" => Some(Category::Dotnet),
+            "ruby" => Some(Category::Ruby),
+            "php" => Some(Category::Php),
+            "elixir" => Some(Category::Elixir),
+            "haskell" => Some(Category::Haskell),
+            "native" => Some(Category::Native),
+            "iac" => Some(Category::Iac),
+            "runtimes" => Some(Category::Runtimes),
             "brew" => Some(Category::Brew),
             "docker" => Some(Category::Docker),
             _ => None,
@@ -28,9 +66,23 @@ impl Category {
             Category::Xcode => "xcode",
             Category::Python => "python",
             Category::Rust => "rust",
+            Category::Rustup => "rustup",
             Category::Nodejs => "nodejs",
+            Category::Go => "go",
+            Category::Jvm => "jvm",
+            Category::Android => "android",
+            Category::Dart => "dart",
+            Category::Dotnet => "dotnet",
+            Category::Ruby => "ruby",
+            Category::Php => "php",
+            Category::Elixir => "elixir",
+            Category::Haskell => "haskell",
+            Category::Native => "native",
+            Category::Iac => "iac",
+            Category::Runtimes => "runtimes",
             Category::Brew => "brew",
             Category::Docker => "docker",
+            Category::Custom(name) => name,
         }
     }
 
@@ -39,14 +91,72 @@ impl Category {
             Category::Xcode => "Xcode",
             Category::Python => "Python",
             Category::Rust => "Rust",
+            Category::Rustup => "Rustup toolchains",
             Category::Nodejs => "Node.js",
+            Category::Go => "Go",
+            Category::Jvm => "JVM",
+            Category::Android => "Android",
+            Category::Dart => "Dart/Flutter",
+            Category::Dotnet => ".NET",
+            Category::Ruby => "Ruby",
+            Category::Php => "PHP",
+            Category::Elixir => "Elixir/Erlang",
+            Category::Haskell => "Haskell",
+            Category::Native => "C/C++",
+            Category::Iac => "Infrastructure as code",
+            Category::Runtimes => "Runtime versions",
             Category::Brew => "Homebrew",
             Category::Docker => "Docker",
+            Category::Custom(name) => {
+                custom::definition(name).map_or(name, |def| def.display_name())
+            }
         }
     }
 
     pub fn supports_current_mode(&self, current: bool) -> bool {
-        !current || !matches!(self, Category::Brew | Category::Docker)
+        match self {
+            Category::Rustup | Category::Runtimes | Category::Brew | Category::Docker => !current,
+            Category::Custom(name) => {
+                !current
+                    || custom::definition(name).is_none_or(|def| def.scope == CustomScope::Project)
+            }
+            _ => true,
+        }
+    }
+
+    /// True for built-ins and for custom categories declared in the current config.
+    pub fn is_defined(&self) -> bool {
+        match self {
+            Category::Custom(name) => custom::definition(name).is_some(),
+            _ => true,
+        }
+    }
+}
+
+/// Keeps names of custom categories that are no longer declared, so journal records, quarantine
+/// manifests, and plans written under an earlier config remain readable.
+fn intern(name: &str) -> &'static str {
+    static INTERNED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+    let mut interned = INTERNED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(existing) = interned.iter().find(|existing| **existing == name) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(name.to_string().into_boxed_str());
+    interned.push(leaked);
+    leaked
+}
+
+impl Serialize for Category {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Category {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Category::from_name(&name).unwrap_or_else(|| Category::Custom(intern(&name))))
     }
 }
 
@@ -63,3 +173,21 @@ impl fmt::Display for Category {
         write!(f, "{}", self.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undeclared_custom_categories_round_trip_through_serde() {
+        let category: Category = serde_json::from_str("\"old-tool\"").expect("category parses");
+        assert_eq!(category, Category::Custom("old-tool"));
+        assert!(!category.is_defined());
+        assert_eq!(category.display_name(), "old-tool");
+        assert_eq!(serde_json::to_string(&category).expect("serializes"), "\"old-tool\"");
+
+        let builtin: Category = serde_json::from_str("\"rust\"").expect("category parses");
+        assert_eq!(builtin, Category::Rust);
+        assert!("old-tool".parse::<Category>().is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::error::AppError;
+
+use super::category::Category;
+use super::item::CleanupItem;
+use super::target::{CleanupTarget, ScanScope};
+
+static REGISTRY: OnceLock<&'static [CustomCategory]> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomScope {
+    /// Matches `names` below the scan roots.
+    #[default]
+    Project,
+    /// Removes fixed `paths`; skipped in `--current` mode.
+    Global,
+}
+
+/// A cleanup category declared with `[[category]]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomCategory {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub scope: CustomScope,
+    /// Directory or file names matched below the scan roots.
+    #[serde(default)]
+    pub names: Vec<String>,
+    /// Sibling that must exist next to a match, like `Cargo.toml` next to a Rust `target`.
+    #[serde(default)]
+    pub marker: Option<String>,
+    /// Absolute paths removed by a global category.
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+impl CustomCategory {
+    pub fn category(&'static self) -> Category {
+        Category::Custom(self.name.as_str())
+    }
+
+    pub fn display_name(&'static self) -> &'static str {
+        self.display_name.as_deref().unwrap_or(&self.name)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let valid_name = !self.name.is_empty()
+            && self.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !valid_name {
+            return Err(format!(
+                "category name '{}' must use lowercase letters, digits, and '-'",
+                self.name
+            ));
+        }
+        if Category::builtin(&self.name).is_some() {
+            return Err(format!("category '{}' is built in", self.name));
+        }
+
+        match self.scope {
+            CustomScope::Project => {
+                if self.names.is_empty() || !self.paths.is_empty() {
+                    return Err(format!(
+                        "project category '{}' needs `names` and no `paths`",
+                        self.name
+                    ));
+                }
+                if self.names.iter().chain(&self.marker).any(|name| name.contains('/')) {
+                    return Err(format!(
+                        "category '{}' names and marker must not contain '/'",
+                        self.name
+                    ));
+                }
+            }
+            CustomScope::Global => {
+                if self.paths.is_empty() || !self.names.is_empty() || self.marker.is_some() {
+                    return Err(format!(
+                        "global category '{}' needs `paths` and no `names` or `marker`",
+                        self.name
+                    ));
+                }
+                if let Some(path) = self.paths.iter().find(|path| !path.is_absolute()) {
+                    return Err(format!(
+                        "global category '{}' path {} must be absolute",
+                        self.name,
+                        path.display()
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        match self.scope {
+            CustomScope::Project => {
+                let name_matches = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| self.names.iter().any(|candidate| candidate == name));
+                name_matches
+                    && self.marker.as_ref().is_none_or(|marker| {
+                        path.parent().is_some_and(|parent| parent.join(marker).exists())
+                    })
+            }
+            CustomScope::Global => self.paths.iter().any(|candidate| candidate == path),
+        }
+    }
+}
+
+/// Registers the custom categories for this process. Must run before any category is parsed.
+pub fn register(definitions: Vec<CustomCategory>) -> Result<(), AppError> {
+    for (index, definition) in definitions.iter().enumerate() {
+        definition.validate().map_err(AppError::Config)?;
+        if definitions[..index].iter().any(|other| other.name == definition.name) {
+            return Err(AppError::Config(format!(
+                "category '{}' is declared twice",
+                definition.name
+            )));
+        }
+    }
+
+    let leaked: &'static [CustomCategory] = Box::leak(definitions.into_boxed_slice());
+    REGISTRY
+        .set(leaked)
+        .map_err(|_| AppError::Config("custom categories are already registered".to_string()))
+}
+
+pub fn definitions() -> &'static [CustomCategory] {
+    REGISTRY.get().copied().unwrap_or(&[])
+}
+
+pub fn definition(name: &str) -> Option<&'static CustomCategory> {
+    definitions().iter().find(|definition| definition.name == name)
+}
+
+pub struct CustomTarget {
+    definition: &'static CustomCategory,
+}
+
+impl CustomTarget {
+    pub fn new(definition: &'static CustomCategory) -> Self {
+        Self { definition }
+    }
+
+    fn collect_matches(&self, scope: &ScanScope) -> Vec<(PathBuf, bool)> {
+        match self.definition.scope {
+            CustomScope::Project => self.collect_project_matches(scope),
+            CustomScope::Global => self
+                .definition
+                .paths
+                .iter()
+                .filter_map(|path| {
+                    fs::symlink_metadata(path)
+                        .ok()
+                        .map(|metadata| (path.clone(), metadata.is_dir()))
+                })
+                .collect(),
+        }
+    }
+
+    fn collect_project_matches(&self, scope: &ScanScope) -> Vec<(PathBuf, bool)> {
+        let mut matches = Vec::new();
+
+        for root in scope.roots() {
+            if !root.exists() {
+                continue;
+            }
+
+            let mut walker = scope.walk(root);
+            while let Some(entry) = walker.next() {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    Err(err) => {
+                        if scope.verbose() {
+                            eprintln!("Skipping {:?}: {}", err.path(), err);
+                        }
+                        continue;
+                    }
+                };
+
+                if entry.depth() == 0 || !self.definition.matches(entry.path()) {
+                    continue;
+                }
+
+                let is_dir = entry.file_type().is_dir();
+                matches.push((entry.path().to_path_buf(), is_dir));
+                if is_dir {
+                    walker.skip_current_dir();
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+impl CleanupTarget for CustomTarget {
+    fn category(&self) -> Category {
+        self.definition.category()
+    }
+
+    fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
+        let category = self.category();
+        Ok(self
+            .collect_matches(scope)
+            .into_iter()
+            .map(|(path, is_dir)| {
+                if is_dir {
+                    CleanupItem::directory(category, path, 0)
+                } else {
+                    CleanupItem::file(category, path, 0)
+                }
+            })
+            .collect())
+    }
+
+    fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
+        let matches = self.collect_matches(scope);
+        if self.definition.scope == CustomScope::Global {
+            return Ok(matches.into_iter().map(|(path, _)| path.display().to_string()).collect());
+        }
+
+        let mut type_counts: BTreeMap<String, usize> = BTreeMap::new();
+        for (path, _) in matches {
+            let name = path.file_name().map(|name| name.to_string_lossy().into_owned());
+            *type_counts.entry(name.unwrap_or_default()).or_insert(0) += 1;
+        }
+
+        Ok(type_counts
+            .into_iter()
+            .map(|(target_type, count)| {
+                format!(
+                    "{} ({} location{} found)",
+                    target_type,
+                    count,
+                    if count == 1 { "" } else { "s" }
+                )
+            })
+            .collect())
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        self.definition.matches(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    fn leak(definition: CustomCategory) -> &'static CustomCategory {
+        Box::leak(Box::new(definition))
+    }
+
+    fn project(names: &[&str], marker: Option<&str>) -> CustomCategory {
+        CustomCategory {
+            name: "gen".to_string(),
+            display_name: Some("Generated".to_string()),
+            scope: CustomScope::Project,
+            names: names.iter().map(|name| name.to_string()).collect(),
+            marker: marker.map(str::to_string),
+            paths: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn project_category_matches_names_next_to_marker() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("tool/gen.yaml").write_str("version: 1").expect("marker exists");
+        temp.child("tool/.gen-cache/blob").write_str("cache").expect("cache exists");
+        temp.child("tool/out-bundle").write_str("bundle").expect("bundle exists");
+        temp.child("other/.gen-cache/blob").write_str("cache").expect("cache exists");
+
+        let target =
+            CustomTarget::new(leak(project(&[".gen-cache", "out-bundle"], Some("gen.yaml"))));
+        let scope = ScanScope::new(vec![temp.path().to_path_buf()], false, false);
+        let mut items = target.discover(&scope).expect("discovery succeeds");
+        items.sort_by(|a, b| a.path.cmp(&b.path));
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].path, temp.path().join("tool/.gen-cache"));
+        assert_eq!(items[0].kind, crate::targets::item::ItemKind::Directory);
+        assert_eq!(items[1].path, temp.path().join("tool/out-bundle"));
+        assert_eq!(items[1].kind, crate::targets::item::ItemKind::File);
+        assert!(items.iter().all(|item| item.category == Category::Custom("gen")));
+        assert!(!target.owns(&temp.path().join("other/.gen-cache")));
+    }
+
+    #[test]
+    fn global_category_reports_existing_paths_only() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("cache/data").write_str("cache").expect("cache exists");
+
+        let target = CustomTarget::new(leak(CustomCategory {
+            name: "tool-cache".to_string(),
+            display_name: None,
+            scope: CustomScope::Global,
+            names: Vec::new(),
+            marker: None,
+            paths: vec![temp.path().join("cache"), temp.path().join("missing")],
+        }));
+        let scope = ScanScope::new(vec![temp.path().to_path_buf()], false, false);
+        let items = target.discover(&scope).expect("discovery succeeds");
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].path, temp.path().join("cache"));
+        assert_eq!(target.category().display_name(), "tool-cache");
+    }
+
+    #[test]
+    fn validate_rejects_builtin_names_and_mixed_scopes() {
+        let mut builtin = project(&["target"], None);
+        builtin.name = "rust".to_string();
+        assert!(builtin.validate().is_err());
+
+        let mut mixed = project(&[".gen-cache"], None);
+        mixed.paths = vec![PathBuf::from("/tmp/gen")];
+        assert!(mixed.validate().is_err());
+
+        let mut uppercase = project(&[".gen-cache"], None);
+        uppercase.name = "Gen".to_string();
+        assert!(uppercase.validate().is_err());
+
+        assert!(project(&[".gen-cache"], Some("gen.yaml")).validate().is_ok());
+    }
+}
@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+/// Marks a platform directory (`ios/`, `android/`) as part of a Flutter project, so its outputs
+/// belong to `dart` rather than `xcode` or `jvm`.
+pub(crate) const FLUTTER_PLATFORM_MARKERS: &[&str] = &["../pubspec.yaml"];
+
+const DART_RULES: &[ProjectRule] = &[
+    ProjectRule::new(".dart_tool", &["pubspec.yaml"]),
+    ProjectRule::new("build", &["pubspec.yaml"]),
+    ProjectRule::new("ios/Pods", &["pubspec.yaml"]),
+    ProjectRule::new("android/.gradle", &["pubspec.yaml"]),
+];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Dart, DART_RULES, global_paths, current)
+}
+
+/// The pub package cache (`PUB_CACHE`, else `~/.pub-cache`) and the engine artifacts of the
+/// Flutter SDK on `PATH`, which `flutter precache` downloads again.
+fn global_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let pub_cache = project::env_dir("PUB_CACHE")
+        .or_else(|| dirs::home_dir().map(|home| home.join(".pub-cache")));
+    paths.extend(pub_cache);
+    paths.extend(flutter_root().map(|sdk| sdk.join("bin/cache/artifacts")));
+    paths
+}
+
+/// `FLUTTER_ROOT`, else the SDK that contains the first `flutter` executable on `PATH`.
+fn flutter_root() -> Option<PathBuf> {
+    if let Some(root) = project::env_dir("FLUTTER_ROOT") {
+        return Some(root);
+    }
+    let search_path = std::env::var_os("PATH")?;
+    let executable = std::env::split_paths(&search_path)
+        .map(|dir| dir.join("flutter"))
+        .find(|candidate| candidate.is_file())?;
+    let executable = executable.canonicalize().ok()?;
+    // `<sdk>/bin/flutter`
+    executable.parent()?.parent().map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::jvm;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+    use crate::targets::xcode::XcodeTarget;
+
+    #[test]
+    fn flutter_platform_outputs_belong_to_dart_only() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let app = temp.child("app");
+        app.child("pubspec.yaml").write_str("name: app").expect("marker exists");
+        app.child(".dart_tool/package_config.json").write_str("{}").expect("artifact exists");
+        app.child("build/app/outputs").create_dir_all().expect("artifact exists");
+        app.child("ios/Podfile").write_str("platform :ios").expect("podfile exists");
+        app.child("ios/Pods/Flutter").create_dir_all().expect("pods exist");
+        app.child("android/settings.gradle").write_str("").expect("gradle settings exist");
+        app.child("android/.gradle/8.3").create_dir_all().expect("gradle cache exists");
+
+        let target = target(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![
+                app.path().join(".dart_tool"),
+                app.path().join("android/.gradle"),
+                app.path().join("build"),
+                app.path().join("ios/Pods"),
+            ]
+        );
+
+        let xcode = XcodeTarget::new(true);
+        assert!(!xcode.owns(&app.path().join("ios/Pods")));
+        assert!(discovered_paths(&xcode, temp.path()).is_empty());
+        let jvm = jvm::target(true);
+        assert!(!jvm.owns(&app.path().join("android/.gradle")));
+        assert!(discovered_paths(&jvm, temp.path()).is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::OnceLock;
 
@@ -11,7 +11,7 @@ use super::category::Category;
 use super::item::CleanupItem;
 use super::target::{CleanupTarget, ScanScope};
 
-const DOCKER_SCAN_LABEL: &str = "docker:prune";
+pub const DOCKER_SCAN_LABEL: &str = "docker:prune";
 static DOCKER_AVAILABLE: OnceLock<bool> = OnceLock::new();
 
 fn probe_docker_available() -> bool {
@@ -96,7 +96,7 @@ impl CleanupTarget for DockerTarget {
     fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
         if !self.available() {
             if scope.verbose() {
-                println!("Docker CLI not available, skipping Docker scan.");
+                eprintln!("Docker CLI not available, skipping Docker scan.");
             }
             return Ok(Vec::new());
         }
@@ -170,4 +170,8 @@ impl CleanupTarget for DockerTarget {
             "Build cache".to_string(),
         ])
     }
+
+    fn owns(&self, path: &Path) -> bool {
+        path == Path::new(DOCKER_SCAN_LABEL)
+    }
 }
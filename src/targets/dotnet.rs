@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+/// `bin` and `obj` are build output only beside a project file; a solution root's `bin` often
+/// holds committed scripts.
+const PROJECT_MARKERS: &[&str] = &["*.csproj", "*.fsproj", "*.vbproj"];
+
+const DOTNET_RULES: &[ProjectRule] = &[
+    ProjectRule::new("bin", PROJECT_MARKERS),
+    ProjectRule::new("obj", PROJECT_MARKERS),
+    // Visual Studio's per-solution cache of indexes and user options.
+    ProjectRule::new(".vs", &["*.sln", "*.slnx"]),
+];
+
+/// NuGet locals as named by `dotnet nuget locals`, with their override variable and default
+/// location under the home directory.
+const NUGET_LOCALS: &[(&str, &str, &str)] = &[
+    ("global-packages", "NUGET_PACKAGES", ".nuget/packages"),
+    ("http-cache", "NUGET_HTTP_CACHE_PATH", ".local/share/NuGet/http-cache"),
+    ("plugins-cache", "NUGET_PLUGINS_CACHE_PATH", ".local/share/NuGet/plugins-cache"),
+];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Dotnet, DOTNET_RULES, global_paths, current)
+}
+
+/// NuGet caches as reported by `dotnet nuget locals all --list`, falling back to the
+/// environment overrides and NuGet's defaults when the SDK is not installed.
+fn global_paths() -> Vec<PathBuf> {
+    let reported = project::command_output("dotnet", &["nuget", "locals", "all", "--list"])
+        .unwrap_or_default();
+    let home = dirs::home_dir();
+
+    NUGET_LOCALS
+        .iter()
+        .filter_map(|(name, variable, default)| {
+            reported_local(&reported, name)
+                .or_else(|| project::env_dir(variable))
+                .or_else(|| home.as_ref().map(|home| home.join(default)))
+        })
+        .collect()
+}
+
+/// Parses a `name: path` line, also accepting the `info : ` prefix of older SDKs.
+fn reported_local(output: &str, name: &str) -> Option<PathBuf> {
+    output.lines().find_map(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("info :").map(str::trim_start).unwrap_or(line);
+        let (key, value) = line.split_once(':')?;
+        let path = PathBuf::from(value.trim());
+        (key.trim() == name && path.is_absolute()).then_some(path)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+
+    #[test]
+    fn discover_reports_project_output_and_solution_caches() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("shop/Shop.sln").write_str("").expect("marker exists");
+        temp.child("shop/bin/deploy.sh").write_str("#!/bin/sh").expect("script exists");
+        temp.child("shop/.vs/Shop/v17").create_dir_all().expect("cache exists");
+        temp.child("shop/Api/Api.csproj").write_str("<Project/>").expect("marker exists");
+        temp.child("shop/Api/bin/Debug").create_dir_all().expect("artifact exists");
+        temp.child("shop/Api/obj/Debug").create_dir_all().expect("artifact exists");
+        temp.child("legacy/Legacy.vbproj").write_str("<Project/>").expect("marker exists");
+        temp.child("legacy/obj/Release").create_dir_all().expect("artifact exists");
+
+        let target = target(true);
+        assert_eq!(
+            discovered_paths(&target, temp.path()),
+            vec![
+                temp.path().join("legacy/obj"),
+                temp.path().join("shop/.vs"),
+                temp.path().join("shop/Api/bin"),
+                temp.path().join("shop/Api/obj"),
+            ]
+        );
+        assert!(!target.owns(&temp.path().join("shop/bin")));
+    }
+
+    #[test]
+    fn reported_local_reads_current_and_legacy_output() {
+        let output = "http-cache: /home/me/.local/share/NuGet/http-cache\n\
+                      info : global-packages: /home/me/.nuget/packages/\n\
+                      temp: /tmp/NuGetScratch";
+
+        assert_eq!(
+            reported_local(output, "global-packages"),
+            Some(PathBuf::from("/home/me/.nuget/packages/"))
+        );
+        assert_eq!(
+            reported_local(output, "http-cache"),
+            Some(PathBuf::from("/home/me/.local/share/NuGet/http-cache"))
+        );
+        assert_eq!(reported_local(output, "plugins-cache"), None);
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+const BEAM_MARKERS: &[&str] = &["mix.exs", "rebar.config"];
+
+const ELIXIR_RULES: &[ProjectRule] =
+    &[ProjectRule::new("_build", BEAM_MARKERS), ProjectRule::new("deps", BEAM_MARKERS)];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Elixir, ELIXIR_RULES, global_paths, current)
+}
+
+/// Package and build caches only: Hex's package tarballs, rebar3's Hex cache and the projects
+/// `Mix.install` builds. The rest of `~/.hex`, `~/.cache/rebar3` and all of `~/.mix` are kept,
+/// since they hold the Hex API key, installed archives and escripts, and `rebar3 local install`.
+fn global_paths() -> Vec<PathBuf> {
+    let hex_home =
+        project::env_dir("HEX_HOME").or_else(|| dirs::home_dir().map(|home| home.join(".hex")));
+    let rebar_cache = project::env_dir("REBAR_CACHE_DIR")
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cache/rebar3")));
+    let mix_installs = project::env_dir("MIX_INSTALL_DIR")
+        .or_else(|| dirs::cache_dir().map(|cache| cache.join("mix/installs")));
+
+    let mut paths = Vec::new();
+    paths.extend(hex_home.map(|hex_home| hex_home.join("packages")));
+    paths.extend(rebar_cache.map(|rebar_cache| rebar_cache.join("hex")));
+    paths.extend(mix_installs);
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+
+    #[test]
+    fn discover_reports_build_and_deps_in_mix_and_rebar_projects() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("phx/mix.exs").write_str("defmodule Phx.MixProject").expect("marker exists");
+        temp.child("phx/_build/dev/lib").create_dir_all().expect("build exists");
+        temp.child("phx/deps/plug/mix.exs").write_str("defmodule Plug").expect("deps exist");
+        temp.child("erl/rebar.config").write_str("{deps, []}.").expect("marker exists");
+        temp.child("erl/_build/default").create_dir_all().expect("build exists");
+        temp.child("infra/deps/terraform").create_dir_all().expect("unrelated deps exist");
+
+        let target = target(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![
+                temp.path().join("erl/_build"),
+                temp.path().join("phx/_build"),
+                temp.path().join("phx/deps"),
+            ]
+        );
+
+        let home = dirs::home_dir().expect("home directory is known");
+        for kept in [".mix", ".mix/archives", ".mix/escripts", ".hex", ".cache/rebar3"] {
+            assert!(!target.owns(&home.join(kept)), "{kept} is kept");
+        }
+    }
+}
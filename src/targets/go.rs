@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+/// Go modules keep no build output of their own (`vendor` is usually committed source), except
+/// GoReleaser's `dist`, which every release run rebuilds from scratch.
+const GO_RULES: &[ProjectRule] =
+    &[ProjectRule::new("dist", &["go.mod"]).requires(&["{.goreleaser,goreleaser}.{yml,yaml}"])];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Go, GO_RULES, global_paths, current)
+}
+
+/// `GOCACHE` and `GOMODCACHE` as reported by `go env`, falling back to Go's defaults when the
+/// toolchain is not installed.
+fn global_paths() -> Vec<PathBuf> {
+    let reported = project::command_output("go", &["env", "GOCACHE", "GOMODCACHE"])
+        .map(|output| output.lines().map(str::trim).map(PathBuf::from).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let mut paths = Vec::new();
+    match reported.first().filter(|path| path.is_absolute()) {
+        Some(cache) => paths.push(cache.clone()),
+        None => paths.extend(dirs::cache_dir().map(|cache| cache.join("go-build"))),
+    }
+    match reported.get(1).filter(|path| path.is_absolute()) {
+        Some(modcache) => paths.push(modcache.clone()),
+        None => paths.extend(default_gopath().map(|gopath| gopath.join("pkg/mod"))),
+    }
+    paths
+}
+
+fn default_gopath() -> Option<PathBuf> {
+    let from_env = std::env::var_os("GOPATH")
+        .and_then(|value| std::env::split_paths(&value).next())
+        .filter(|path| path.is_absolute());
+    from_env.or_else(|| dirs::home_dir().map(|home| home.join("go")))
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+
+    #[test]
+    fn discover_reports_goreleaser_dist_in_go_modules_only() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("cli/go.mod").write_str("module example.com/cli").expect("marker exists");
+        temp.child("cli/.goreleaser.yaml").write_str("version: 2").expect("config exists");
+        temp.child("cli/dist/cli_linux_amd64").create_dir_all().expect("output exists");
+        temp.child("svc/go.mod").write_str("module example.com/svc").expect("marker exists");
+        temp.child("svc/dist/openapi.yaml").write_str("openapi: 3.1.0").expect("file exists");
+        temp.child("web/goreleaser.yml").write_str("version: 2").expect("config exists");
+        temp.child("web/dist/index.html").write_str("<html/>").expect("file exists");
+
+        let target = target(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(paths, vec![temp.path().join("cli/dist")]);
+        assert!(!target.owns(&temp.path().join("svc/dist")));
+        assert!(!target.owns(&temp.path().join("web/dist")));
+    }
+}
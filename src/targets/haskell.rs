@@ -0,0 +1,63 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+const HASKELL_RULES: &[ProjectRule] = &[
+    ProjectRule::new("dist-newstyle", &["*.cabal", "cabal.project"]),
+    ProjectRule::new(".stack-work", &["stack.yaml"]),
+];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Haskell, HASKELL_RULES, global_paths, current)
+}
+
+/// Cabal's package download cache (legacy and XDG layouts) and Stack's snapshot builds and
+/// Pantry index. Stack's `config.yaml` and installed GHCs under `programs` are kept.
+fn global_paths() -> Vec<PathBuf> {
+    let home = dirs::home_dir();
+    let cabal_dir =
+        project::env_dir("CABAL_DIR").or_else(|| home.as_ref().map(|home| home.join(".cabal")));
+    let stack_root =
+        project::env_dir("STACK_ROOT").or_else(|| home.as_ref().map(|home| home.join(".stack")));
+
+    let mut paths = Vec::new();
+    paths.extend(cabal_dir.map(|cabal| cabal.join("packages")));
+    paths.extend(dirs::cache_dir().map(|cache| cache.join("cabal/packages")));
+    if let Some(stack_root) = stack_root {
+        paths.push(stack_root.join("snapshots"));
+        paths.push(stack_root.join("pantry"));
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+
+    #[test]
+    fn discover_reports_cabal_and_stack_build_dirs() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("lib/parser.cabal").write_str("name: parser").expect("marker exists");
+        temp.child("lib/dist-newstyle/build").create_dir_all().expect("build exists");
+        temp.child("app/stack.yaml").write_str("resolver: lts-22.0").expect("marker exists");
+        temp.child("app/.stack-work/dist").create_dir_all().expect("build exists");
+        temp.child("loose/.stack-work/dist").create_dir_all().expect("directory exists");
+
+        let target = target(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![temp.path().join("app/.stack-work"), temp.path().join("lib/dist-newstyle")]
+        );
+        assert!(!target.owns(&temp.path().join("loose/.stack-work")));
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+const TERRAFORM_MARKERS: &[&str] = &["*.tf", "*.tf.json"];
+const SERVERLESS_MARKERS: &[&str] =
+    &["serverless.yml", "serverless.yaml", "serverless.ts", "serverless.js", "serverless.json"];
+const SAM_MARKERS: &[&str] = &["template.yaml", "template.yml", "samconfig.toml"];
+
+/// Downloaded providers and modules are restored by `terraform init`. The rest of `.terraform`
+/// (backend settings and the selected workspace) is kept so the next command targets the same
+/// workspace.
+const IAC_RULES: &[ProjectRule] = &[
+    ProjectRule::new(".terraform/providers", TERRAFORM_MARKERS),
+    ProjectRule::new(".terraform/modules", TERRAFORM_MARKERS),
+    ProjectRule::new(".terraform/plugins", TERRAFORM_MARKERS),
+    ProjectRule::new("cdk.out", &["cdk.json"]),
+    ProjectRule::new(".serverless", SERVERLESS_MARKERS),
+    ProjectRule::new(".aws-sam/build", SAM_MARKERS),
+];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Iac, IAC_RULES, global_paths, current)
+}
+
+/// The Terraform plugin cache (`TF_PLUGIN_CACHE_DIR`, else `~/.terraform.d/plugin-cache`) and
+/// Pulumi's plugins (under `PULUMI_HOME`, else `~/.pulumi`).
+fn global_paths() -> Vec<PathBuf> {
+    let home = dirs::home_dir();
+    let plugin_cache = project::env_dir("TF_PLUGIN_CACHE_DIR")
+        .or_else(|| home.as_ref().map(|home| home.join(".terraform.d/plugin-cache")));
+    let pulumi_home =
+        project::env_dir("PULUMI_HOME").or_else(|| home.as_ref().map(|home| home.join(".pulumi")));
+
+    let mut paths = Vec::new();
+    paths.extend(plugin_cache);
+    paths.extend(pulumi_home.map(|pulumi| pulumi.join("plugins")));
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+
+    #[test]
+    fn discover_reports_downloads_and_synth_output_but_keeps_terraform_state() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("network/main.tf").write_str("terraform {}").expect("marker exists");
+        temp.child("network/.terraform/providers/registry.terraform.io")
+            .create_dir_all()
+            .expect("providers exist");
+        temp.child("network/.terraform/modules/modules.json").write_str("{}").expect("ok");
+        temp.child("network/.terraform/environment").write_str("prod").expect("workspace exists");
+        temp.child("stack/cdk.json").write_str("{}").expect("marker exists");
+        temp.child("stack/cdk.out/manifest.json").write_str("{}").expect("synth output exists");
+        temp.child("api/template.yaml").write_str("Resources: {}").expect("marker exists");
+        temp.child("api/.aws-sam/build/Fn").create_dir_all().expect("build exists");
+        temp.child("stray/.terraform/providers").create_dir_all().expect("directory exists");
+
+        let target = target(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![
+                temp.path().join("api/.aws-sam/build"),
+                temp.path().join("network/.terraform/modules"),
+                temp.path().join("network/.terraform/providers"),
+                temp.path().join("stack/cdk.out"),
+            ]
+        );
+        assert!(!target.owns(&temp.path().join("stray/.terraform/providers")));
+    }
+}
@@ -1,28 +1,49 @@
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use super::category::Category;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ItemKind {
     File,
     Directory,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CleanupItem {
     pub category: Category,
     pub path: PathBuf,
     pub size: u64,
     pub kind: ItemKind,
+    /// Newest modification time inside the item or among its project's files; set while sizing.
+    #[serde(default)]
+    pub modified: Option<DateTime<Utc>>,
+    /// Directory whose marker matched the item, when a rule found it. Its top-level files count
+    /// toward the item's age; items without one use their parent directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<PathBuf>,
 }
 
 impl CleanupItem {
     pub fn directory(category: Category, path: PathBuf, size: u64) -> Self {
-        Self { category, path, size, kind: ItemKind::Directory }
+        Self { category, path, size, kind: ItemKind::Directory, modified: None, project: None }
     }
 
     pub fn file(category: Category, path: PathBuf, size: u64) -> Self {
-        Self { category, path, size, kind: ItemKind::File }
+        Self { category, path, size, kind: ItemKind::File, modified: None, project: None }
+    }
+
+    pub fn in_project(mut self, project: PathBuf) -> Self {
+        self.project = Some(project);
+        self
+    }
+
+    pub fn age(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.modified.map(|modified| (now - modified).to_std().unwrap_or_default())
     }
 
     pub fn is_zero(&self) -> bool {
@@ -33,3 +54,55 @@ impl CleanupItem {
         &self.path
     }
 }
+
+/// Bounds from `--older-than` / `--newer-than`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AgeFilter {
+    pub older_than: Option<Duration>,
+    pub newer_than: Option<Duration>,
+}
+
+impl AgeFilter {
+    pub fn is_active(&self) -> bool {
+        self.older_than.is_some() || self.newer_than.is_some()
+    }
+
+    /// Items without a known age (such as Docker's reclaimable space) never pass an active filter.
+    pub fn matches(&self, item: &CleanupItem, now: DateTime<Utc>) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        let Some(age) = item.age(now) else {
+            return false;
+        };
+        self.older_than.is_none_or(|bound| age >= bound)
+            && self.newer_than.is_none_or(|bound| age < bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn age_filter_bounds_items_by_modification_time() {
+        let now = Utc::now();
+        let day = Duration::from_secs(86_400);
+        let mut old = CleanupItem::directory(Category::Rust, PathBuf::from("/a/target"), 0);
+        old.modified = Some(now - chrono::Duration::days(40));
+        let mut fresh = CleanupItem::directory(Category::Rust, PathBuf::from("/b/target"), 0);
+        fresh.modified = Some(now - chrono::Duration::hours(2));
+        let unknown = CleanupItem::directory(Category::Docker, PathBuf::from("docker:prune"), 0);
+
+        let older = AgeFilter { older_than: Some(30 * day), newer_than: None };
+        assert!(older.matches(&old, now));
+        assert!(!older.matches(&fresh, now));
+        assert!(!older.matches(&unknown, now));
+
+        let newer = AgeFilter { older_than: None, newer_than: Some(day) };
+        assert!(!newer.matches(&old, now));
+        assert!(newer.matches(&fresh, now));
+
+        assert!(AgeFilter::default().matches(&unknown, now));
+    }
+}
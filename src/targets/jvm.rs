@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::android::ANDROID_MODULE_MARKERS;
+use super::category::Category;
+use super::dart::FLUTTER_PLATFORM_MARKERS;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+const GRADLE_MARKERS: &[&str] =
+    &["build.gradle", "build.gradle.kts", "settings.gradle", "settings.gradle.kts"];
+
+const JVM_RULES: &[ProjectRule] = &[
+    ProjectRule::new("build", GRADLE_MARKERS).unless(ANDROID_MODULE_MARKERS),
+    ProjectRule::new(".gradle", GRADLE_MARKERS).unless(FLUTTER_PLATFORM_MARKERS),
+    // A `Cargo.toml` next to `target/` means the directory belongs to Rust.
+    ProjectRule::new("target", &["pom.xml"]).unless(&["Cargo.toml"]),
+];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Jvm, JVM_RULES, global_paths, current)
+}
+
+fn global_paths() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let gradle_home = project::env_dir("GRADLE_USER_HOME").unwrap_or_else(|| home.join(".gradle"));
+
+    vec![gradle_home.join("caches"), gradle_home.join("wrapper/dists"), home.join(".m2/repository")]
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+
+    #[test]
+    fn discover_attributes_target_to_maven_only_without_cargo() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("api/pom.xml").write_str("<project/>").expect("marker exists");
+        temp.child("api/target/classes/App.class").write_str("cafe").expect("artifact exists");
+        temp.child("web/settings.gradle.kts").write_str("").expect("marker exists");
+        temp.child("web/build/libs/app.jar").write_str("jar").expect("artifact exists");
+        temp.child("web/.gradle/8.5/checksums").create_dir_all().expect("artifact exists");
+        temp.child("jni/pom.xml").write_str("<project/>").expect("marker exists");
+        temp.child("jni/Cargo.toml").write_str("[package]").expect("marker exists");
+        temp.child("jni/target/debug").create_dir_all().expect("artifact exists");
+        temp.child("docs/build/html").create_dir_all().expect("unrelated build exists");
+
+        let target = target(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![
+                temp.path().join("api/target"),
+                temp.path().join("web/.gradle"),
+                temp.path().join("web/build"),
+            ]
+        );
+    }
+}
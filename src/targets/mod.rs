@@ -1,12 +1,27 @@
+pub mod android;
 pub mod brew;
 pub mod catalog;
 pub mod category;
+pub mod custom;
+pub mod dart;
 pub mod docker;
+pub mod dotnet;
+pub mod elixir;
+pub mod go;
+pub mod haskell;
+pub mod iac;
 pub mod item;
+pub mod jvm;
 pub mod name_matcher;
+pub mod native;
 pub mod nodejs;
+pub mod php;
+pub mod project;
 pub mod python;
 pub mod report;
+pub mod ruby;
+pub mod runtimes;
 pub mod rust;
+pub mod rustup;
 pub mod target;
 pub mod xcode;
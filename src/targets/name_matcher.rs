@@ -1,7 +1,5 @@
 use std::collections::{BTreeMap, HashSet};
 
-use walkdir::WalkDir;
-
 use crate::error::AppError;
 
 use super::category::Category;
@@ -26,7 +24,7 @@ impl NameMatcherTarget {
                 continue;
             }
 
-            let mut walker = WalkDir::new(root).max_depth(10).into_iter();
+            let mut walker = scope.walk(root);
             while let Some(entry) = walker.next() {
                 let entry = match entry {
                     Ok(entry) => entry,
@@ -83,4 +81,10 @@ impl CleanupTarget for NameMatcherTarget {
 
         Ok(targets)
     }
+
+    fn owns(&self, path: &std::path::Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| self.targets.contains(&name))
+    }
 }
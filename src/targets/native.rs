@@ -0,0 +1,212 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use dirs_next as dirs;
+
+use crate::error::AppError;
+
+use super::category::Category;
+use super::item::CleanupItem;
+use super::project::{self, ContentRule, RuleTable};
+use super::target::{CleanupTarget, ScanScope};
+
+/// Out-of-source build trees, whatever their name. A tree that holds its own sources or a
+/// repository is an in-source build and is never reported.
+const NATIVE_RULES: &[ContentRule] = &[
+    ContentRule::new("CMake build", &["CMakeCache.txt"]).unless(&["CMakeLists.txt", ".git"]),
+    ContentRule::new("Meson build", &["meson-private", "build.ninja"])
+        .unless(&["meson.build", ".git"]),
+];
+
+const BAZEL_MARKERS: &[&str] = &["MODULE.bazel", "WORKSPACE", "WORKSPACE.bazel"];
+const BAZEL_LINK_PREFIX: &str = "bazel-";
+
+pub struct NativeTarget {
+    rules: RuleTable<ContentRule>,
+    current: bool,
+}
+
+impl NativeTarget {
+    pub fn new(current: bool) -> Self {
+        Self { rules: RuleTable::new(NATIVE_RULES), current }
+    }
+
+    /// The ccache directory: `CCACHE_DIR`, otherwise its default location in the platform cache
+    /// directory and the legacy `~/.ccache`. sccache belongs to the rust category.
+    fn global_paths() -> Vec<PathBuf> {
+        project::env_dir("CCACHE_DIR").map(|dir| vec![dir]).unwrap_or_else(|| {
+            let legacy = dirs::home_dir().map(|home| home.join(".ccache"));
+            dirs::cache_dir().map(|cache| cache.join("ccache")).into_iter().chain(legacy).collect()
+        })
+    }
+
+    fn is_bazel_link(path: &Path) -> bool {
+        path.file_name().is_some_and(|name| name.to_string_lossy().starts_with(BAZEL_LINK_PREFIX))
+            && fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+            && path.parent().is_some_and(|workspace| {
+                BAZEL_MARKERS.iter().any(|marker| workspace.join(marker).exists())
+            })
+    }
+
+    /// Bazel's `bazel-*` convenience symlinks in workspaces under the scan roots.
+    fn bazel_links(scope: &ScanScope) -> Vec<PathBuf> {
+        let mut links = Vec::new();
+
+        for root in scope.roots() {
+            if !root.exists() {
+                continue;
+            }
+
+            for entry in scope.walk(root).flatten() {
+                if entry.depth() > 0 && entry.path_is_symlink() && Self::is_bazel_link(entry.path())
+                {
+                    links.push(entry.into_path());
+                }
+            }
+        }
+
+        links
+    }
+
+    /// The output bases the links resolve into (`<output_base>/execroot/<workspace>/…`).
+    fn output_bases(links: &[PathBuf]) -> BTreeSet<PathBuf> {
+        links
+            .iter()
+            .filter_map(|link| fs::canonicalize(link).ok())
+            .filter_map(|target| {
+                target
+                    .ancestors()
+                    .find(|ancestor| ancestor.file_name().is_some_and(|name| name == "execroot"))
+                    .and_then(Path::parent)
+                    .map(Path::to_path_buf)
+            })
+            .filter(|base| Self::is_output_base(base))
+            .collect()
+    }
+
+    /// Bazel keeps output bases in `_bazel_<user>/<hash>` directories.
+    fn is_output_base(path: &Path) -> bool {
+        path.join("execroot").is_dir()
+            && path
+                .parent()
+                .and_then(Path::file_name)
+                .is_some_and(|name| name.to_string_lossy().starts_with("_bazel_"))
+    }
+}
+
+impl CleanupTarget for NativeTarget {
+    fn category(&self) -> Category {
+        Category::Native
+    }
+
+    fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
+        let artifacts = project::discover_artifacts(scope, &self.rules);
+        let mut items = project::artifact_items(Category::Native, artifacts);
+
+        let links = Self::bazel_links(scope);
+        if !self.current {
+            items.extend(
+                Self::output_bases(&links)
+                    .into_iter()
+                    .map(|base| CleanupItem::directory(Category::Native, base, 0)),
+            );
+            items.extend(project::global_items(Category::Native, &Self::global_paths()));
+        }
+        items.extend(links.into_iter().map(|link| CleanupItem::file(Category::Native, link, 0)));
+        Ok(items)
+    }
+
+    fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
+        let mut targets = project::list_artifacts(&project::discover_artifacts(scope, &self.rules));
+
+        let links = Self::bazel_links(scope);
+        if !links.is_empty() {
+            targets.push(project::location_count("Bazel links (bazel-*)", links.len()));
+        }
+        if !self.current {
+            targets.extend(
+                Self::output_bases(&links)
+                    .iter()
+                    .map(|base| format!("{} (Bazel output base)", base.display())),
+            );
+            targets.extend(project::list_global(&Self::global_paths()));
+        }
+        Ok(targets)
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        self.rules.matches(path)
+            || Self::is_bazel_link(path)
+            || Self::is_output_base(path)
+            || Self::global_paths().iter().any(|candidate| candidate == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+
+    #[test]
+    fn discover_recognizes_build_trees_by_content_and_skips_in_source_builds() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("engine/CMakeLists.txt").write_str("project(engine)").expect("source exists");
+        temp.child("engine/out/rel/CMakeCache.txt").write_str("CMAKE_HOME").expect("cache exists");
+        temp.child("inplace/CMakeLists.txt").write_str("project(x)").expect("source exists");
+        temp.child("inplace/CMakeCache.txt").write_str("CMAKE_HOME").expect("cache exists");
+        temp.child("lib/meson.build").write_str("project('lib')").expect("source exists");
+        temp.child("lib/builddir/build.ninja").write_str("rule cc").expect("ninja file exists");
+        temp.child("lib/builddir/meson-private/coredata.dat").write_str("").expect("state exists");
+
+        let target = NativeTarget::new(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![temp.path().join("engine/out/rel"), temp.path().join("lib/builddir")]
+        );
+        assert!(!target.owns(&temp.path().join("inplace")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn discover_reports_bazel_links_and_their_output_base() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let output_base = temp.child("cache/_bazel_dev/0f3c");
+        output_base.child("execroot/app/bazel-out/k8-fastbuild/bin").create_dir_all().expect("ok");
+        temp.child("app/MODULE.bazel").write_str("module(name = \"app\")").expect("marker exists");
+        std::os::unix::fs::symlink(
+            output_base.path().join("execroot/app/bazel-out"),
+            temp.path().join("app/bazel-out"),
+        )
+        .expect("link is created");
+        std::os::unix::fs::symlink(
+            output_base.path().join("execroot/app/bazel-out/k8-fastbuild/bin"),
+            temp.path().join("app/bazel-bin"),
+        )
+        .expect("link is created");
+
+        let target = NativeTarget::new(false);
+        let scope = ScanScope::new(vec![temp.path().join("app")], false, false);
+        let mut paths: Vec<_> = target
+            .discover(&scope)
+            .expect("scan succeeds")
+            .into_iter()
+            .map(|item| item.path)
+            .filter(|path| path.starts_with(temp.path()))
+            .collect();
+        paths.sort();
+
+        let canonical_base = fs::canonicalize(output_base.path()).expect("base resolves");
+        assert!(paths.contains(&temp.path().join("app/bazel-bin")));
+        assert!(paths.contains(&temp.path().join("app/bazel-out")));
+        assert!(
+            paths.contains(&canonical_base) || paths.contains(&output_base.path().to_path_buf())
+        );
+        assert!(target.owns(&temp.path().join("app/bazel-out")));
+    }
+}
@@ -1,36 +1,130 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use dirs_next as dirs;
+
+use crate::config::expand_home;
 use crate::error::AppError;
 
 use super::category::Category;
 use super::item::CleanupItem;
 use super::name_matcher::NameMatcherTarget;
+use super::project;
 use super::target::{CleanupTarget, ScanScope};
 
 const NODEJS_TARGETS: &[&str] = &["node_modules", ".next", ".nuxt", ".svelte-kit"];
 
-pub struct NodejsTarget(NameMatcherTarget);
+pub struct NodejsTarget {
+    matcher: NameMatcherTarget,
+    current: bool,
+}
 
 impl NodejsTarget {
-    pub fn new() -> Self {
-        Self(NameMatcherTarget::new(Category::Nodejs, NODEJS_TARGETS))
+    pub fn new(current: bool) -> Self {
+        Self { matcher: NameMatcherTarget::new(Category::Nodejs, NODEJS_TARGETS), current }
     }
-}
 
-impl Default for NodejsTarget {
-    fn default() -> Self {
-        Self::new()
+    /// Package-manager caches shared by every project: npm's `_cacache`, Yarn classic and Berry,
+    /// the pnpm store, Bun's install cache and `DENO_DIR`. Each honours the tool's own override
+    /// before falling back to its default locations.
+    fn global_paths() -> Vec<PathBuf> {
+        let home = dirs::home_dir();
+        let cache = dirs::cache_dir();
+        let mut paths = Vec::new();
+
+        let npm_cache = project::env_dir("npm_config_cache")
+            .or_else(npmrc_cache)
+            .or_else(|| home.as_ref().map(|home| home.join(".npm")));
+        paths.extend(npm_cache.map(|npm| npm.join("_cacache")));
+
+        match project::env_dir("YARN_CACHE_FOLDER") {
+            Some(yarn) => paths.push(yarn),
+            None => paths
+                .extend(cache.iter().flat_map(|cache| [cache.join("yarn"), cache.join("Yarn")])),
+        }
+        let berry_global = project::env_dir("YARN_GLOBAL_FOLDER")
+            .or_else(|| home.as_ref().map(|home| home.join(".yarn/berry")));
+        paths.extend(berry_global.map(|global| global.join("cache")));
+
+        match project::env_dir("npm_config_store_dir") {
+            Some(store) => paths.push(store),
+            None => {
+                paths.extend(project::env_dir("PNPM_HOME").map(|pnpm| pnpm.join("store")));
+                paths.extend(dirs::data_local_dir().map(|data| data.join("pnpm/store")));
+                paths.extend(home.as_ref().map(|home| home.join("Library/pnpm/store")));
+            }
+        }
+
+        let bun_cache = project::env_dir("BUN_INSTALL_CACHE_DIR").or_else(|| {
+            project::env_dir("BUN_INSTALL")
+                .or_else(|| home.as_ref().map(|home| home.join(".bun")))
+                .map(|bun| bun.join("install/cache"))
+        });
+        paths.extend(bun_cache);
+
+        let deno_dir =
+            project::env_dir("DENO_DIR").or_else(|| cache.as_ref().map(|cache| cache.join("deno")));
+        paths.extend(deno_dir);
+
+        paths.sort();
+        paths.dedup();
+        paths
     }
 }
 
+/// The `cache=` setting of the user's `.npmrc` (`NPM_CONFIG_USERCONFIG`, else `~/.npmrc`).
+fn npmrc_cache() -> Option<PathBuf> {
+    let npmrc = project::env_dir("NPM_CONFIG_USERCONFIG")
+        .or_else(|| dirs::home_dir().map(|home| home.join(".npmrc")))?;
+    parse_npmrc_cache(&fs::read_to_string(npmrc).ok()?)
+}
+
+fn parse_npmrc_cache(contents: &str) -> Option<PathBuf> {
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        let path = expand_home(Path::new(value.trim()));
+        (key.trim() == "cache" && path.is_absolute()).then_some(path)
+    })
+}
+
 impl CleanupTarget for NodejsTarget {
     fn category(&self) -> Category {
-        self.0.category()
+        self.matcher.category()
     }
 
     fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
-        self.0.discover(scope)
+        let mut items = self.matcher.discover(scope)?;
+        if !self.current {
+            items.extend(project::global_items(Category::Nodejs, &Self::global_paths()));
+        }
+        Ok(items)
     }
 
     fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
-        self.0.list(scope)
+        let mut targets = self.matcher.list(scope)?;
+        if !self.current {
+            targets.extend(project::list_global(&Self::global_paths()));
+        }
+        Ok(targets)
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        self.matcher.owns(path) || Self::global_paths().iter().any(|candidate| candidate == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_npmrc_cache_reads_absolute_and_home_relative_paths() {
+        assert_eq!(
+            parse_npmrc_cache("registry=https://registry.npmjs.org/\ncache = /data/npm\n"),
+            Some(PathBuf::from("/data/npm"))
+        );
+        assert!(parse_npmrc_cache("cache=~/npm-cache").is_some_and(|path| path.is_absolute()));
+        assert_eq!(parse_npmrc_cache("cache=relative/npm"), None);
+        assert_eq!(parse_npmrc_cache("prefix=/usr/local"), None);
     }
 }
@@ -0,0 +1,55 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+/// Composer's `vendor`, restored by `composer install`. A vendored Ruby bundle inside it leaves
+/// the directory to the ruby category.
+const PHP_RULES: &[ProjectRule] =
+    &[ProjectRule::new("vendor", &["composer.json"]).unless(&["vendor/bundle"])];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Php, PHP_RULES, global_paths, current)
+}
+
+/// `COMPOSER_CACHE_DIR` when set, otherwise Composer's legacy and XDG cache locations.
+fn global_paths() -> Vec<PathBuf> {
+    if let Some(cache) = project::env_dir("COMPOSER_CACHE_DIR") {
+        return vec![cache];
+    }
+
+    let mut paths = Vec::new();
+    paths.extend(dirs::home_dir().map(|home| home.join(".composer/cache")));
+    paths.extend(dirs::cache_dir().map(|cache| cache.join("composer")));
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+
+    #[test]
+    fn discover_reports_vendor_only_next_to_composer_json() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("laravel/composer.json").write_str("{}").expect("marker exists");
+        temp.child("laravel/vendor/autoload.php").write_str("<?php").expect("vendor exists");
+        temp.child("svc/go.mod").write_str("module svc").expect("marker exists");
+        temp.child("svc/vendor/modules.txt").write_str("# deps").expect("vendor exists");
+        temp.child("misc/vendor/lib.js").write_str("js").expect("vendor exists");
+
+        let target = target(true);
+        assert_eq!(
+            discovered_paths(&target, temp.path()),
+            vec![temp.path().join("laravel/vendor")]
+        );
+        assert!(!target.owns(&temp.path().join("misc/vendor")));
+        assert!(!target.owns(&temp.path().join("svc/vendor")));
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use globset::{Glob, GlobMatcher};
+
+use crate::error::AppError;
+
+use super::category::Category;
+use super::item::CleanupItem;
+use super::target::{CleanupTarget, ScanScope};
+
+/// Recognizes artifact directories during a walk; `label` groups them in `--list` output.
+pub trait ArtifactRule: Copy + 'static {
+    fn label(&self) -> &'static str;
+    /// Glob patterns the rule matches names against, compiled once by [`RuleTable`].
+    fn patterns(&self) -> Vec<&'static str>;
+    fn matches(&self, path: &Path, globs: &Globs) -> bool;
+    /// The directory whose markers matched `path`, for a path this rule matched.
+    fn project_dir<'a>(&self, path: &'a Path) -> Option<&'a Path>;
+}
+
+/// A project-local artifact: `path` below a project directory that contains at least one of
+/// `markers`, every one of `requires` and none of `unless`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectRule {
+    /// Artifact location relative to the project directory, such as `build` or `app/build`. A
+    /// component may be a glob, as in `*.egg-info`.
+    pub path: &'static str,
+    /// Paths relative to the project directory. A final component with glob metacharacters,
+    /// such as `*.csproj`, matches any entry of its directory.
+    pub markers: &'static [&'static str],
+    /// Markers that must all be present as well, such as the tool config that owns the output.
+    pub requires: &'static [&'static str],
+    /// Markers that attribute the directory to another ecosystem (e.g. `Cargo.toml` for `target`).
+    pub unless: &'static [&'static str],
+}
+
+impl ProjectRule {
+    pub const fn new(path: &'static str, markers: &'static [&'static str]) -> Self {
+        Self { path, markers, requires: &[], unless: &[] }
+    }
+
+    pub const fn requires(mut self, requires: &'static [&'static str]) -> Self {
+        self.requires = requires;
+        self
+    }
+
+    pub const fn unless(mut self, unless: &'static [&'static str]) -> Self {
+        self.unless = unless;
+        self
+    }
+
+    fn components(&self) -> impl DoubleEndedIterator<Item = &'static str> {
+        Path::new(self.path).components().filter_map(|component| component.as_os_str().to_str())
+    }
+
+    /// The project directory that `path` belongs to, if `path` ends with this rule's location.
+    fn matched_project_dir<'a>(&self, path: &'a Path, globs: &Globs) -> Option<&'a Path> {
+        let depth = self.components().count();
+        let tail_matches = path.components().count() > depth
+            && self
+                .components()
+                .rev()
+                .zip(path.components().rev())
+                .all(|(rule, actual)| globs.matches(rule, actual.as_os_str()));
+        if !tail_matches {
+            return None;
+        }
+        path.ancestors().nth(depth)
+    }
+}
+
+impl ArtifactRule for ProjectRule {
+    fn label(&self) -> &'static str {
+        self.path
+    }
+
+    fn patterns(&self) -> Vec<&'static str> {
+        let markers = self.markers.iter().chain(self.requires).chain(self.unless);
+        self.components().chain(markers.filter_map(|marker| marker_name(marker))).collect()
+    }
+
+    fn matches(&self, path: &Path, globs: &Globs) -> bool {
+        self.matched_project_dir(path, globs).is_some_and(|project| {
+            self.markers.iter().any(|marker| globs.has_marker(project, marker))
+                && self.requires.iter().all(|marker| globs.has_marker(project, marker))
+                && !self.unless.iter().any(|marker| globs.has_marker(project, marker))
+        })
+    }
+
+    fn project_dir<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        path.ancestors().nth(self.components().count())
+    }
+}
+
+/// A build directory recognized by what it contains rather than by its name, such as a CMake
+/// tree holding `CMakeCache.txt`. Every marker must exist inside the directory, and none of
+/// `unless`, which guards against in-source builds.
+#[derive(Debug, Clone, Copy)]
+pub struct ContentRule {
+    pub label: &'static str,
+    pub markers: &'static [&'static str],
+    pub unless: &'static [&'static str],
+}
+
+impl ContentRule {
+    pub const fn new(label: &'static str, markers: &'static [&'static str]) -> Self {
+        Self { label, markers, unless: &[] }
+    }
+
+    pub const fn unless(mut self, unless: &'static [&'static str]) -> Self {
+        self.unless = unless;
+        self
+    }
+}
+
+impl ArtifactRule for ContentRule {
+    fn label(&self) -> &'static str {
+        self.label
+    }
+
+    fn patterns(&self) -> Vec<&'static str> {
+        self.markers.iter().chain(self.unless).filter_map(|marker| marker_name(marker)).collect()
+    }
+
+    fn matches(&self, dir: &Path, globs: &Globs) -> bool {
+        self.markers.iter().all(|marker| globs.has_marker(dir, marker))
+            && !self.unless.iter().any(|marker| globs.has_marker(dir, marker))
+    }
+
+    /// A build tree is matched by its own contents, so its project is the directory holding it.
+    fn project_dir<'a>(&self, path: &'a Path) -> Option<&'a Path> {
+        path.parent()
+    }
+}
+
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// The final component of a marker path, the only part that may be a glob.
+fn marker_name(marker: &'static str) -> Option<&'static str> {
+    Path::new(marker).file_name().and_then(OsStr::to_str)
+}
+
+/// Compiled matchers for the glob patterns of a rule table, keyed by pattern.
+#[derive(Default)]
+pub struct Globs(HashMap<&'static str, GlobMatcher>);
+
+impl Globs {
+    fn compile(patterns: impl IntoIterator<Item = &'static str>) -> Self {
+        let matchers = patterns
+            .into_iter()
+            .filter(|pattern| is_glob(pattern))
+            .filter_map(|pattern| Some((pattern, Glob::new(pattern).ok()?.compile_matcher())))
+            .collect();
+        Self(matchers)
+    }
+
+    /// Matches a single path component against `pattern`, which may be a glob such as
+    /// `*.egg-info`.
+    fn matches(&self, pattern: &str, name: &OsStr) -> bool {
+        if !is_glob(pattern) {
+            return name == pattern;
+        }
+        self.0.get(pattern).is_some_and(|matcher| matcher.is_match(name))
+    }
+
+    fn has_marker(&self, project: &Path, marker: &str) -> bool {
+        if !is_glob(marker) {
+            return project.join(marker).exists();
+        }
+
+        let marker = Path::new(marker);
+        let (Some(dir), Some(pattern)) = (marker.parent(), marker.file_name()) else {
+            return false;
+        };
+        let Some(matcher) = pattern.to_str().and_then(|pattern| self.0.get(pattern)) else {
+            return false;
+        };
+        fs::read_dir(project.join(dir))
+            .is_ok_and(|entries| entries.flatten().any(|entry| matcher.is_match(entry.file_name())))
+    }
+}
+
+/// A rule table with its glob patterns compiled once, when the target that owns it is built.
+pub struct RuleTable<R: ArtifactRule> {
+    rules: &'static [R],
+    globs: Globs,
+}
+
+impl<R: ArtifactRule> RuleTable<R> {
+    pub fn new(rules: &'static [R]) -> Self {
+        let globs = Globs::compile(rules.iter().flat_map(ArtifactRule::patterns));
+        Self { rules, globs }
+    }
+
+    /// The first rule that matches `path`.
+    pub fn find(&self, path: &Path) -> Option<R> {
+        self.rules.iter().copied().find(|rule| rule.matches(path, &self.globs))
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        self.find(path).is_some()
+    }
+}
+
+/// Walks the scan roots and returns every artifact matched by `rules`, without descending into
+/// matched artifacts.
+pub fn discover_artifacts<R: ArtifactRule>(
+    scope: &ScanScope,
+    rules: &RuleTable<R>,
+) -> Vec<(PathBuf, R)> {
+    let mut matches = Vec::new();
+    if rules.rules.is_empty() {
+        return matches;
+    }
+
+    for root in scope.roots() {
+        if !root.exists() {
+            continue;
+        }
+
+        let mut walker = scope.walk(root);
+        while let Some(entry) = walker.next() {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    if scope.verbose() {
+                        eprintln!("Skipping {:?}: {}", err.path(), err);
+                    }
+                    continue;
+                }
+            };
+
+            if entry.depth() == 0 || !entry.file_type().is_dir() {
+                continue;
+            }
+
+            if let Some(rule) = rules.find(entry.path()) {
+                matches.push((entry.path().to_path_buf(), rule));
+                walker.skip_current_dir();
+            }
+        }
+    }
+
+    matches
+}
+
+pub fn artifact_items<R: ArtifactRule>(
+    category: Category,
+    artifacts: Vec<(PathBuf, R)>,
+) -> Vec<CleanupItem> {
+    artifacts
+        .into_iter()
+        .map(|(path, rule)| {
+            let project = rule.project_dir(&path).map(Path::to_path_buf);
+            let item = CleanupItem::directory(category, path, 0);
+            match project {
+                Some(project) => item.in_project(project),
+                None => item,
+            }
+        })
+        .collect()
+}
+
+/// `--list` lines counting artifacts per rule label.
+pub fn list_artifacts<R: ArtifactRule>(artifacts: &[(PathBuf, R)]) -> Vec<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for (_, rule) in artifacts {
+        *counts.entry(rule.label()).or_insert(0) += 1;
+    }
+
+    counts.into_iter().map(|(label, count)| location_count(label, count)).collect()
+}
+
+pub fn location_count(label: &str, count: usize) -> String {
+    format!("{} ({} location{} found)", label, count, if count == 1 { "" } else { "s" })
+}
+
+/// Global cache items for the paths that exist. A symlinked cache is a file item, so only the
+/// link is removed and what it points to is left alone.
+pub fn global_items(category: Category, paths: &[PathBuf]) -> Vec<CleanupItem> {
+    paths
+        .iter()
+        .filter(|path| path.exists())
+        .map(|path| {
+            if path.is_symlink() || path.is_file() {
+                CleanupItem::file(category, path.clone(), 0)
+            } else {
+                CleanupItem::directory(category, path.clone(), 0)
+            }
+        })
+        .collect()
+}
+
+pub fn list_global(paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .filter(|path| path.exists())
+        .map(|path| format!("{} (exists)", path.display()))
+        .collect()
+}
+
+/// A category made of project-local artifacts plus global caches. Global caches are skipped in
+/// `--current` mode.
+pub struct ProjectTarget {
+    category: Category,
+    rules: RuleTable<ProjectRule>,
+    global_paths: fn() -> Vec<PathBuf>,
+    /// Resolved once, since `global_paths` may run the ecosystem's tool and `owns` is called for
+    /// every item of a plan being revalidated.
+    resolved_globals: OnceLock<Vec<PathBuf>>,
+    current: bool,
+}
+
+impl ProjectTarget {
+    pub fn new(
+        category: Category,
+        rules: &'static [ProjectRule],
+        global_paths: fn() -> Vec<PathBuf>,
+        current: bool,
+    ) -> Self {
+        Self {
+            category,
+            rules: RuleTable::new(rules),
+            global_paths,
+            resolved_globals: OnceLock::new(),
+            current,
+        }
+    }
+
+    fn global_paths(&self) -> &[PathBuf] {
+        self.resolved_globals.get_or_init(self.global_paths)
+    }
+}
+
+impl CleanupTarget for ProjectTarget {
+    fn category(&self) -> Category {
+        self.category
+    }
+
+    fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
+        let artifacts = discover_artifacts(scope, &self.rules);
+        let mut items = artifact_items(self.category, artifacts);
+        if !self.current {
+            items.extend(global_items(self.category, self.global_paths()));
+        }
+        Ok(items)
+    }
+
+    fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
+        let mut targets = list_artifacts(&discover_artifacts(scope, &self.rules));
+        if !self.current {
+            targets.extend(list_global(self.global_paths()));
+        }
+        Ok(targets)
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        self.rules.matches(path) || self.global_paths().iter().any(|candidate| candidate == path)
+    }
+}
+
+/// An absolute directory from the environment; relative values are ignored.
+pub fn env_dir(variable: &str) -> Option<PathBuf> {
+    std::env::var_os(variable).map(PathBuf::from).filter(|path| path.is_absolute())
+}
+
+/// Trimmed stdout of a successful command, or `None` when the tool is missing or fails.
+pub fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).stderr(Stdio::null()).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Sorted paths that `target` discovers below `root` in `--current` mode.
+#[cfg(test)]
+pub(crate) fn discovered_paths(target: &dyn CleanupTarget, root: &Path) -> Vec<PathBuf> {
+    let scope = ScanScope::new(vec![root.to_path_buf()], true, false);
+    let mut paths: Vec<_> =
+        target.discover(&scope).expect("scan succeeds").into_iter().map(|item| item.path).collect();
+    paths.sort();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    const RULES: &[ProjectRule] = &[
+        ProjectRule::new("target", &["pom.xml"]).unless(&["Cargo.toml"]),
+        ProjectRule::new("app/build", &["settings.gradle"]),
+        ProjectRule::new("obj", &["*.csproj"]),
+        ProjectRule::new("*.egg-info", &["setup.py"]),
+    ];
+
+    #[test]
+    fn discover_artifacts_honors_markers_unless_and_nested_paths() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("maven/pom.xml").write_str("<project/>").expect("marker exists");
+        temp.child("maven/target/classes").create_dir_all().expect("artifact exists");
+        temp.child("mixed/pom.xml").write_str("<project/>").expect("marker exists");
+        temp.child("mixed/Cargo.toml").write_str("[package]").expect("marker exists");
+        temp.child("mixed/target/debug").create_dir_all().expect("artifact exists");
+        temp.child("android/settings.gradle").write_str("").expect("marker exists");
+        temp.child("android/app/build/outputs").create_dir_all().expect("artifact exists");
+        temp.child("plain/target").create_dir_all().expect("directory exists");
+        temp.child("api/Api.csproj").write_str("<Project/>").expect("marker exists");
+        temp.child("api/obj/Debug").create_dir_all().expect("artifact exists");
+        temp.child("notes/Api.csproj.bak/obj").create_dir_all().expect("directory exists");
+        temp.child("pkg/setup.py").write_str("setup()").expect("marker exists");
+        temp.child("pkg/pkg.egg-info/PKG-INFO").write_str("Name: pkg").expect("artifact exists");
+
+        let scope = ScanScope::new(vec![temp.path().to_path_buf()], false, false);
+        let rules = RuleTable::new(RULES);
+        let mut found: Vec<_> =
+            discover_artifacts(&scope, &rules).into_iter().map(|(path, _)| path).collect();
+        found.sort();
+
+        assert_eq!(
+            found,
+            vec![
+                temp.path().join("android/app/build"),
+                temp.path().join("api/obj"),
+                temp.path().join("maven/target"),
+                temp.path().join("pkg/pkg.egg-info"),
+            ]
+        );
+        assert!(rules.find(&temp.path().join("mixed/target")).is_none());
+        let globs = Globs::compile(["*.egg-info", "bazel-*"]);
+        assert!(globs.matches("*.egg-info", OsStr::new("a.b.egg-info")));
+        assert!(!globs.matches("*.egg-info", OsStr::new("egg-info")));
+        assert!(globs.matches("bazel-*", OsStr::new("bazel-")));
+    }
+
+    fn temp_cache() -> Vec<PathBuf> {
+        vec![std::env::temp_dir()]
+    }
+
+    #[test]
+    fn project_target_reports_global_caches_outside_current_mode_only() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("maven/pom.xml").write_str("<project/>").expect("marker exists");
+        temp.child("maven/target/classes").create_dir_all().expect("artifact exists");
+        let scope = ScanScope::new(vec![temp.path().to_path_buf()], false, false);
+        let paths = |target: &ProjectTarget| -> Vec<PathBuf> {
+            target
+                .discover(&scope)
+                .expect("scan succeeds")
+                .into_iter()
+                .map(|item| item.path)
+                .collect()
+        };
+
+        let global = ProjectTarget::new(Category::Jvm, RULES, temp_cache, false);
+        assert_eq!(paths(&global), vec![temp.path().join("maven/target"), std::env::temp_dir()]);
+        assert!(global.owns(&std::env::temp_dir()));
+
+        let current = ProjectTarget::new(Category::Jvm, RULES, temp_cache, true);
+        assert_eq!(paths(&current), vec![temp.path().join("maven/target")]);
+    }
+
+    static RESOLUTIONS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counted_cache() -> Vec<PathBuf> {
+        RESOLUTIONS.fetch_add(1, Ordering::SeqCst);
+        vec![std::env::temp_dir()]
+    }
+
+    #[test]
+    fn project_target_resolves_global_caches_once() {
+        let target = ProjectTarget::new(Category::Go, &[], counted_cache, false);
+        for _ in 0..3 {
+            assert!(target.owns(&std::env::temp_dir()));
+            assert!(!target.owns(Path::new("/elsewhere")));
+        }
+        assert_eq!(RESOLUTIONS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,37 +1,174 @@
+use std::path::{Path, PathBuf};
+
+use dirs_next as dirs;
+
 use crate::error::AppError;
 
 use super::category::Category;
 use super::item::CleanupItem;
 use super::name_matcher::NameMatcherTarget;
+use super::project::{self, ProjectRule, RuleTable};
 use super::target::{CleanupTarget, ScanScope};
 
 const PYTHON_TARGETS: &[&str] =
-    &["__pycache__", ".pytest_cache", ".ruff_cache", ".mypy_cache", ".venv"];
+    &["__pycache__", ".pytest_cache", ".ruff_cache", ".mypy_cache", ".venv", ".uv-cache"];
+
+const PACKAGING_MARKERS: &[&str] = &["pyproject.toml", "setup.py"];
 
-pub struct PythonTarget(NameMatcherTarget);
+/// Packaging output, whose generic names are only claimed inside a Python project, and tox and
+/// nox environments, claimed only next to their configuration.
+const PYTHON_RULES: &[ProjectRule] = &[
+    ProjectRule::new(".tox", &["tox.ini", "tox.toml"]),
+    ProjectRule::new(".nox", &["noxfile.py"]),
+    ProjectRule::new("build", PACKAGING_MARKERS),
+    ProjectRule::new("dist", PACKAGING_MARKERS),
+    ProjectRule::new("*.egg-info", PACKAGING_MARKERS),
+    ProjectRule::new("src/*.egg-info", PACKAGING_MARKERS),
+];
+
+pub struct PythonTarget {
+    matcher: NameMatcherTarget,
+    rules: RuleTable<ProjectRule>,
+    current: bool,
+}
 
 impl PythonTarget {
-    pub fn new() -> Self {
-        Self(NameMatcherTarget::new(Category::Python, PYTHON_TARGETS))
+    pub fn new(current: bool) -> Self {
+        Self {
+            matcher: NameMatcherTarget::new(Category::Python, PYTHON_TARGETS),
+            rules: RuleTable::new(PYTHON_RULES),
+            current,
+        }
     }
-}
 
-impl Default for PythonTarget {
-    fn default() -> Self {
-        Self::new()
+    /// Packaging artifacts outside the directories the name matcher already reports, such as
+    /// `build` inside a `.venv`.
+    fn packaging_artifacts(
+        &self,
+        scope: &ScanScope,
+        named: &[CleanupItem],
+    ) -> Vec<(PathBuf, ProjectRule)> {
+        let mut artifacts = project::discover_artifacts(scope, &self.rules);
+        artifacts.retain(|(path, _)| !named.iter().any(|item| path.starts_with(&item.path)));
+        artifacts
+    }
+
+    /// Download and build caches of pip, uv, Poetry, pipx and pre-commit, honouring
+    /// `PIP_CACHE_DIR`, `UV_CACHE_DIR`, `POETRY_CACHE_DIR`, `PIPX_HOME` and `PRE_COMMIT_HOME`.
+    /// Poetry's virtualenvs and pipx-installed tools are kept.
+    fn global_paths() -> Vec<PathBuf> {
+        let home = dirs::home_dir();
+        let cache = dirs::cache_dir();
+        // uv and pre-commit use `~/.cache` on every platform.
+        let xdg_cache = home.as_ref().map(|home| home.join(".cache"));
+        let in_caches = |name: &str| -> Vec<PathBuf> {
+            cache.iter().chain(&xdg_cache).map(|dir| dir.join(name)).collect()
+        };
+        let mut paths = Vec::new();
+
+        paths.extend(
+            project::env_dir("PIP_CACHE_DIR").map_or_else(|| in_caches("pip"), |dir| vec![dir]),
+        );
+        paths.extend(
+            project::env_dir("UV_CACHE_DIR").map_or_else(|| in_caches("uv"), |dir| vec![dir]),
+        );
+
+        let poetry = project::env_dir("POETRY_CACHE_DIR")
+            .map_or_else(|| in_caches("pypoetry"), |dir| vec![dir]);
+        for poetry in poetry {
+            paths.push(poetry.join("cache"));
+            paths.push(poetry.join("artifacts"));
+        }
+
+        match project::env_dir("PIPX_HOME") {
+            Some(pipx) => paths.push(pipx.join(".cache")),
+            None => {
+                paths.extend(home.as_ref().map(|home| home.join(".local/pipx/.cache")));
+                paths.extend(in_caches("pipx"));
+            }
+        }
+
+        paths.extend(
+            project::env_dir("PRE_COMMIT_HOME")
+                .map_or_else(|| in_caches("pre-commit"), |dir| vec![dir]),
+        );
+
+        paths.sort();
+        paths.dedup();
+        paths
     }
 }
 
 impl CleanupTarget for PythonTarget {
     fn category(&self) -> Category {
-        self.0.category()
+        self.matcher.category()
     }
 
     fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
-        self.0.discover(scope)
+        let mut items = self.matcher.discover(scope)?;
+        let artifacts = self.packaging_artifacts(scope, &items);
+        items.extend(project::artifact_items(Category::Python, artifacts));
+        if !self.current {
+            items.extend(project::global_items(Category::Python, &Self::global_paths()));
+        }
+        Ok(items)
     }
 
     fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
-        self.0.list(scope)
+        let mut targets = self.matcher.list(scope)?;
+        let named = self.matcher.discover(scope)?;
+        targets.extend(project::list_artifacts(&self.packaging_artifacts(scope, &named)));
+        if !self.current {
+            targets.extend(project::list_global(&Self::global_paths()));
+        }
+        Ok(targets)
+    }
+
+    fn owns(&self, path: &Path) -> bool {
+        self.matcher.owns(path)
+            || self.rules.matches(path)
+            || Self::global_paths().iter().any(|candidate| candidate == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+
+    #[test]
+    fn discover_reports_packaging_output_only_in_python_projects() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let lib = temp.child("lib");
+        lib.child("pyproject.toml").write_str("[project]").expect("marker exists");
+        lib.child("tox.ini").write_str("[tox]").expect("tox config exists");
+        lib.child("build/lib/pkg.py").write_str("x = 1").expect("build exists");
+        lib.child("dist/pkg-1.0.tar.gz").write_str("sdist").expect("dist exists");
+        lib.child("src/pkg.egg-info/PKG-INFO").write_str("Name: pkg").expect("egg-info exists");
+        lib.child(".tox/py312/build").create_dir_all().expect("tox env exists");
+        lib.child(".tox/py312/pyproject.toml").write_str("[project]").expect("nested marker");
+        temp.child("site/dist/index.html").write_str("<html>").expect("unrelated dist exists");
+        temp.child("site/.nox/docs").create_dir_all().expect("unrelated nox dir exists");
+        temp.child("cli/noxfile.py").write_str("import nox").expect("nox config exists");
+        temp.child("cli/.nox/tests").create_dir_all().expect("nox env exists");
+
+        let target = PythonTarget::new(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![
+                temp.path().join("cli/.nox"),
+                lib.path().join(".tox"),
+                lib.path().join("build"),
+                lib.path().join("dist"),
+                lib.path().join("src/pkg.egg-info"),
+            ]
+        );
+        assert!(!target.owns(&temp.path().join("site/dist")));
+        assert!(!target.owns(&temp.path().join("site/.nox")));
     }
 }
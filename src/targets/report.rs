@@ -1,9 +1,11 @@
 use std::collections::BTreeMap;
 
+use serde::{Deserialize, Serialize};
+
 use super::category::Category;
 use super::item::CleanupItem;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryReport {
     pub category: Category,
     pub items: Vec<CleanupItem>,
@@ -23,7 +25,7 @@ impl CategoryReport {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanReport {
     pub categories: BTreeMap<Category, CategoryReport>,
 }
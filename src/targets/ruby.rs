@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use dirs_next as dirs;
+
+use super::category::Category;
+use super::project::{self, ProjectRule, ProjectTarget};
+
+/// Gems installed with `bundle config set path vendor/bundle`, and Bundler's per-project state.
+const RUBY_RULES: &[ProjectRule] =
+    &[ProjectRule::new("vendor/bundle", &["Gemfile"]), ProjectRule::new(".bundle", &["Gemfile"])];
+
+pub fn target(current: bool) -> ProjectTarget {
+    ProjectTarget::new(Category::Ruby, RUBY_RULES, global_paths, current)
+}
+
+/// Bundler's download cache, RubyGems' spec cache and the `.gem` archives kept in the gem
+/// directory reported by `gem env gemdir`. System gem directories are left alone.
+fn global_paths() -> Vec<PathBuf> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+
+    let mut paths = vec![home.join(".bundle/cache"), home.join(".gem/specs")];
+    let gem_cache = project::command_output("gem", &["env", "gemdir"])
+        .map(|gemdir| PathBuf::from(gemdir).join("cache"))
+        .filter(|cache| cache.is_absolute() && cache.starts_with(&home));
+    paths.extend(gem_cache);
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+    use crate::targets::project::discovered_paths;
+    use crate::targets::target::CleanupTarget;
+
+    #[test]
+    fn discover_reports_bundle_dirs_only_next_to_gemfile() {
+        let temp = TempDir::new().expect("temp directory is created");
+        temp.child("rails/Gemfile").write_str("source 'https://rubygems.org'").expect("ok");
+        temp.child("rails/vendor/bundle/ruby/3.3.0/gems").create_dir_all().expect("gems exist");
+        temp.child("rails/vendor/assets/app.js").write_str("js").expect("assets exist");
+        temp.child("rails/.bundle/config").write_str("BUNDLE_PATH: vendor/bundle").expect("ok");
+        temp.child("loose/vendor/bundle/gems").create_dir_all().expect("directory exists");
+
+        let target = target(true);
+        let paths = discovered_paths(&target, temp.path());
+
+        assert_eq!(
+            paths,
+            vec![temp.path().join("rails/.bundle"), temp.path().join("rails/vendor/bundle")]
+        );
+        assert!(!target.owns(&temp.path().join("loose/vendor/bundle")));
+    }
+}
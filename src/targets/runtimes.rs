@@ -0,0 +1,606 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use dirs_next as dirs;
+
+use crate::error::AppError;
+
+use super::category::Category;
+use super::item::CleanupItem;
+use super::project;
+use super::target::{CleanupTarget, ScanScope};
+
+/// Project files that select a runtime version, read by nvm, pyenv, rbenv, asdf and mise.
+const PIN_FILES: &[&str] = &[
+    ".nvmrc",
+    ".node-version",
+    ".python-version",
+    ".ruby-version",
+    ".tool-versions",
+    "mise.toml",
+    ".mise.toml",
+];
+
+/// Pin values that name no installed version (`system`) or the newest one, which is always kept:
+/// nvm's `node`, `stable` and `current`, and `latest`.
+const NEWEST_OR_SYSTEM: &[&str] = &["system", "latest", "node", "stable", "current"];
+
+/// How many nvm alias files are followed before giving up, in case they form a cycle.
+const MAX_ALIAS_DEPTH: usize = 8;
+
+/// A version manager's install tree: `<root>/<version>` for single-tool managers, otherwise
+/// `<root>/<tool>/<version>`.
+struct Manager {
+    root: PathBuf,
+    tool: Option<&'static str>,
+}
+
+/// An installed runtime version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Installation {
+    path: PathBuf,
+    tool: String,
+    version: String,
+}
+
+/// Tool name as mise stores it: asdf's `nodejs` plugin is `node`, and backend tools such as
+/// `cargo:ripgrep` are installed as `cargo-ripgrep`.
+fn normalize_tool(tool: &str) -> String {
+    match tool {
+        "nodejs" => "node".to_string(),
+        _ => tool.replace(':', "-"),
+    }
+}
+
+/// Numeric components of a version such as `20.11.0` or `v18`; names like `lts` or
+/// `miniconda3-latest` have none.
+fn version_key(version: &str) -> Option<Vec<u64>> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Whether `pin` selects `installed`: an exact match, or a prefix that stops at a component
+/// boundary, so `20` selects `20.11.0` but `3.1` does not select `3.12.1`.
+fn version_matches(installed: &str, pin: &str) -> bool {
+    let installed = installed.strip_prefix('v').unwrap_or(installed);
+    let pin = pin.strip_prefix('v').unwrap_or(pin);
+    installed == pin || installed.strip_prefix(pin).is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// `(tool, version)` pairs a pin file selects.
+fn read_pin_file(name: &str, contents: &str) -> Vec<(String, String)> {
+    let lines = || {
+        contents
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty())
+    };
+    let pin = |tool: &str, version: &str| (tool.to_string(), version.to_string());
+
+    match name {
+        ".nvmrc" | ".node-version" => lines().take(1).map(|version| pin("node", version)).collect(),
+        ".python-version" => lines().map(|version| pin("python", version)).collect(),
+        ".ruby-version" => lines()
+            .take(1)
+            .map(|version| pin("ruby", version.strip_prefix("ruby-").unwrap_or(version)))
+            .collect(),
+        ".tool-versions" => lines()
+            .flat_map(|line| {
+                let mut fields = line.split_whitespace();
+                let tool = normalize_tool(fields.next().unwrap_or_default());
+                fields.map(move |version| (tool.clone(), version.to_string()))
+            })
+            .collect(),
+        _ => read_mise_tools(contents),
+    }
+}
+
+/// The `[tools]` table of a mise config, whose values are a version, a list of versions, or a
+/// table with a `version` key.
+fn read_mise_tools(contents: &str) -> Vec<(String, String)> {
+    let Ok(table) = contents.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let Some(tools) = table.get("tools").and_then(|tools| tools.as_table()) else {
+        return Vec::new();
+    };
+
+    let mut pins = Vec::new();
+    for (tool, value) in tools {
+        let versions: Vec<&toml::Value> = match value {
+            toml::Value::Array(versions) => versions.iter().collect(),
+            toml::Value::Table(options) => options.get("version").into_iter().collect(),
+            value => vec![value],
+        };
+        pins.extend(
+            versions
+                .into_iter()
+                .filter_map(toml::Value::as_str)
+                .map(|version| (normalize_tool(tool), version.to_string())),
+        );
+    }
+    pins
+}
+
+/// Pins reduced to installed versions, plus the tools with a pin that names nothing installed.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ResolvedPins {
+    versions: Vec<(String, String)>,
+    unresolved_tools: Vec<String>,
+}
+
+/// Follows nvm alias files (`$NVM_DIR/alias/<name>`), so `lts/*`, `lts/iron` or a user alias
+/// becomes the version it finally names.
+fn follow_nvm_alias(nvm_dir: &Path, pin: &str) -> String {
+    let mut version = pin.to_string();
+    for _ in 0..MAX_ALIAS_DEPTH {
+        if version_key(&version).is_some() {
+            break;
+        }
+        let Ok(contents) = fs::read_to_string(nvm_dir.join("alias").join(&version)) else {
+            break;
+        };
+        match contents.trim() {
+            "" => break,
+            next => version = next.to_string(),
+        }
+    }
+    version
+}
+
+/// The installed version `pin` reaches through one of the tool's directories: an alias symlink
+/// such as a pyenv-virtualenv environment or mise's `20`, or a path like `3.11.4/envs/myenv`.
+fn aliased_version(
+    pin: &str,
+    tool: &str,
+    tool_dirs: &[(PathBuf, String)],
+    installations: &[Installation],
+) -> Option<String> {
+    tool_dirs.iter().filter(|(_, dir_tool)| dir_tool == tool).find_map(|(dir, _)| {
+        let target = fs::canonicalize(dir.join(pin)).ok()?;
+        installations
+            .iter()
+            .filter(|installation| installation.tool == tool)
+            .find(|installation| {
+                fs::canonicalize(&installation.path).is_ok_and(|path| target.starts_with(path))
+            })
+            .map(|installation| installation.version.clone())
+    })
+}
+
+/// Resolves aliases in `pins`. A tool whose pin cannot be resolved lands in `unresolved_tools`,
+/// since any of its versions might be the one the pin means.
+fn resolve_pins(
+    pins: &[(String, String)],
+    installations: &[Installation],
+    tool_dirs: &[(PathBuf, String)],
+    nvm_dir: Option<&Path>,
+) -> ResolvedPins {
+    let mut resolved = ResolvedPins::default();
+    for (tool, pin) in pins {
+        let version = match nvm_dir {
+            Some(nvm_dir) if tool == "node" => follow_nvm_alias(nvm_dir, pin),
+            _ => pin.clone(),
+        };
+        let installed = installations
+            .iter()
+            .any(|installation| installation.tool == *tool && installation.version == version);
+
+        if version_key(&version).is_some() || installed {
+            resolved.versions.push((tool.clone(), version));
+        } else if let Some(version) = aliased_version(&version, tool, tool_dirs, installations) {
+            resolved.versions.push((tool.clone(), version));
+        } else if !NEWEST_OR_SYSTEM.contains(&version.as_str())
+            && !resolved.unresolved_tools.contains(tool)
+        {
+            resolved.unresolved_tools.push(tool.clone());
+        }
+    }
+    resolved
+}
+
+/// Installations no pin selects. Per manager and tool, the newest version is always kept, as are
+/// versions that are not plain numbers and every version of a tool with an unresolved pin.
+fn unreferenced(installations: Vec<Installation>, pins: &ResolvedPins) -> Vec<PathBuf> {
+    let mut newest: HashMap<(Option<&Path>, &str), Vec<u64>> = HashMap::new();
+    for installation in &installations {
+        if let Some(key) = version_key(&installation.version) {
+            let slot =
+                newest.entry((installation.path.parent(), installation.tool.as_str())).or_default();
+            if key > *slot {
+                *slot = key;
+            }
+        }
+    }
+
+    let mut outdated: Vec<PathBuf> = installations
+        .iter()
+        .filter(|installation| !pins.unresolved_tools.contains(&installation.tool))
+        .filter(|installation| {
+            let Some(key) = version_key(&installation.version) else {
+                return false;
+            };
+            let pinned = pins.versions.iter().any(|(tool, version)| {
+                *tool == installation.tool && version_matches(&installation.version, version)
+            });
+            let is_newest = newest
+                .get(&(installation.path.parent(), installation.tool.as_str()))
+                .is_some_and(|newest| *newest == key);
+            !pinned && !is_newest
+        })
+        .map(|installation| installation.path.clone())
+        .collect();
+    outdated.sort();
+    outdated
+}
+
+/// Real directories below `parent`. Aliases such as mise's `latest` or pyenv-virtualenv's
+/// environments are symlinks; they are not installations and only count through the pins that
+/// name them.
+fn subdirectories(parent: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(entries) = fs::read_dir(parent) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .map(|entry| (entry.path(), entry.file_name().to_string_lossy().into_owned()))
+        .collect()
+}
+
+/// Runtime versions that no pin selects. Opt-in like rustup: a version used by a project outside
+/// the scan roots would be lost, so the category never runs by default or with `--all`.
+pub struct RuntimesTarget {
+    /// The scope whose pins protect versions when a saved plan is re-checked.
+    scope: ScanScope,
+}
+
+impl RuntimesTarget {
+    pub fn new(scope: ScanScope) -> Self {
+        Self { scope }
+    }
+
+    fn home_dir(variable: &str, default: &str) -> Option<PathBuf> {
+        project::env_dir(variable).or_else(|| dirs::home_dir().map(|home| home.join(default)))
+    }
+
+    fn nvm_dir() -> Option<PathBuf> {
+        Self::home_dir("NVM_DIR", ".nvm")
+    }
+
+    fn pyenv_root() -> Option<PathBuf> {
+        Self::home_dir("PYENV_ROOT", ".pyenv")
+    }
+
+    fn rbenv_root() -> Option<PathBuf> {
+        Self::home_dir("RBENV_ROOT", ".rbenv")
+    }
+
+    /// `MISE_DATA_DIR`, else `mise` in `XDG_DATA_HOME` or `~/.local/share` (also on macOS).
+    fn mise_data_dir() -> Option<PathBuf> {
+        project::env_dir("MISE_DATA_DIR").or_else(|| {
+            Self::home_dir("XDG_DATA_HOME", ".local/share").map(|data| data.join("mise"))
+        })
+    }
+
+    fn managers() -> Vec<Manager> {
+        let mut managers = Vec::new();
+        if let Some(nvm) = Self::nvm_dir() {
+            managers.push(Manager { root: nvm.join("versions/node"), tool: Some("node") });
+        }
+        if let Some(pyenv) = Self::pyenv_root() {
+            managers.push(Manager { root: pyenv.join("versions"), tool: Some("python") });
+        }
+        if let Some(rbenv) = Self::rbenv_root() {
+            managers.push(Manager { root: rbenv.join("versions"), tool: Some("ruby") });
+        }
+        if let Some(asdf) = Self::home_dir("ASDF_DATA_DIR", ".asdf") {
+            managers.push(Manager { root: asdf.join("installs"), tool: None });
+        }
+        if let Some(mise) = Self::mise_data_dir() {
+            managers.push(Manager { root: mise.join("installs"), tool: None });
+        }
+        managers
+    }
+
+    /// Every `(directory, tool)` whose subdirectories are installed versions.
+    fn tool_dirs() -> Vec<(PathBuf, String)> {
+        let mut tool_dirs = Vec::new();
+        for manager in Self::managers() {
+            match manager.tool {
+                Some(tool) => tool_dirs.push((manager.root, tool.to_string())),
+                None => tool_dirs.extend(
+                    subdirectories(&manager.root)
+                        .into_iter()
+                        .map(|(path, tool)| (path, normalize_tool(&tool))),
+                ),
+            }
+        }
+        tool_dirs
+    }
+
+    fn installations(tool_dirs: &[(PathBuf, String)]) -> Vec<Installation> {
+        tool_dirs
+            .iter()
+            .flat_map(|(tool_dir, tool)| {
+                subdirectories(tool_dir).into_iter().map(|(path, version)| Installation {
+                    path,
+                    tool: tool.clone(),
+                    version,
+                })
+            })
+            .collect()
+    }
+
+    /// User-wide defaults: nvm's `default` alias, the pyenv and rbenv global `version` files,
+    /// `~/.tool-versions`, and mise's global config.
+    fn global_pins() -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        if let Some(nvm) = Self::nvm_dir() {
+            files.push((".nvmrc", nvm.join("alias/default")));
+        }
+        if let Some(pyenv) = Self::pyenv_root() {
+            files.push((".python-version", pyenv.join("version")));
+        }
+        if let Some(rbenv) = Self::rbenv_root() {
+            files.push((".ruby-version", rbenv.join("version")));
+        }
+        if let Some(home) = dirs::home_dir() {
+            files.push((".tool-versions", home.join(".tool-versions")));
+        }
+        let mise_config = project::env_dir("MISE_CONFIG_DIR").or_else(|| {
+            Self::home_dir("XDG_CONFIG_HOME", ".config").map(|config| config.join("mise"))
+        });
+        if let Some(mise_config) = mise_config {
+            files.push(("mise.toml", mise_config.join("config.toml")));
+        }
+
+        files
+            .into_iter()
+            .filter_map(|(name, path)| {
+                fs::read_to_string(path).ok().map(|contents| read_pin_file(name, &contents))
+            })
+            .flatten()
+            .collect()
+    }
+
+    fn pins(scope: &ScanScope) -> Vec<(String, String)> {
+        let mut pins = Self::global_pins();
+
+        for root in scope.roots() {
+            if !root.exists() {
+                continue;
+            }
+
+            for entry in scope.walk(root).flatten() {
+                let name = entry.file_name().to_string_lossy();
+                if entry.file_type().is_file()
+                    && PIN_FILES.contains(&name.as_ref())
+                    && let Ok(contents) = fs::read_to_string(entry.path())
+                {
+                    pins.extend(read_pin_file(&name, &contents));
+                }
+            }
+        }
+
+        pins
+    }
+
+    fn unused_versions(scope: &ScanScope) -> Vec<PathBuf> {
+        let tool_dirs = Self::tool_dirs();
+        let installations = Self::installations(&tool_dirs);
+        let pins = resolve_pins(
+            &Self::pins(scope),
+            &installations,
+            &tool_dirs,
+            Self::nvm_dir().as_deref(),
+        );
+        unreferenced(installations, &pins)
+    }
+
+    /// The command that rebuilds the shims of the manager owning the removed version `path`.
+    /// nvm has no shims.
+    fn shim_command(path: &Path) -> Option<(&'static str, Vec<String>)> {
+        let parent = path.parent()?;
+        let is_below =
+            |root: Option<PathBuf>, dir: &str| root.is_some_and(|root| parent == root.join(dir));
+
+        if is_below(Self::pyenv_root(), "versions") {
+            return Some(("pyenv", vec!["rehash".to_string()]));
+        }
+        if is_below(Self::rbenv_root(), "versions") {
+            return Some(("rbenv", vec!["rehash".to_string()]));
+        }
+        let installs = parent.parent()?;
+        if Self::home_dir("ASDF_DATA_DIR", ".asdf")
+            .is_some_and(|asdf| installs == asdf.join("installs"))
+        {
+            let plugin = parent.file_name()?.to_string_lossy().into_owned();
+            return Some(("asdf", vec!["reshim".to_string(), plugin]));
+        }
+        if Self::mise_data_dir().is_some_and(|mise| installs == mise.join("installs")) {
+            return Some(("mise", vec!["reshim".to_string()]));
+        }
+        None
+    }
+}
+
+/// Rebuilds the shims of every manager whose versions were among `items`, so pyenv, rbenv, asdf
+/// and mise do not keep shims that point at removed binaries. A manager that is not installed is
+/// skipped, and one that fails only produces a warning.
+pub fn refresh_shims(items: &[CleanupItem], verbose: bool) {
+    let mut commands = Vec::new();
+    for item in items.iter().filter(|item| item.category == Category::Runtimes) {
+        if let Some(command) = RuntimesTarget::shim_command(&item.path)
+            && !commands.contains(&command)
+        {
+            commands.push(command);
+        }
+    }
+
+    for (program, args) in commands {
+        let command_line = format!("{program} {}", args.join(" "));
+        if verbose {
+            println!("$ {command_line}");
+        }
+        let output = if verbose { Stdio::inherit } else { Stdio::null };
+        match Command::new(program).args(&args).stdout(output()).stderr(output()).status() {
+            Ok(status) if status.success() => {}
+            Ok(status) => eprintln!("Warning: {command_line} failed with status {status}"),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => eprintln!("Warning: {command_line}: {err}"),
+        }
+    }
+}
+
+impl CleanupTarget for RuntimesTarget {
+    fn category(&self) -> Category {
+        Category::Runtimes
+    }
+
+    fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
+        Ok(project::global_items(Category::Runtimes, &Self::unused_versions(scope)))
+    }
+
+    fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
+        Ok(Self::unused_versions(scope)
+            .iter()
+            .map(|path| format!("{} (not referenced)", path.display()))
+            .collect())
+    }
+
+    /// Re-reads the pins, so a version pinned after a plan was saved is no longer claimed.
+    fn owns(&self, path: &Path) -> bool {
+        Self::unused_versions(&self.scope).iter().any(|candidate| candidate == path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    fn installation(path: &str, tool: &str) -> Installation {
+        let version = Path::new(path).file_name().expect("path has a name").to_string_lossy();
+        Installation {
+            path: PathBuf::from(path),
+            tool: tool.to_string(),
+            version: version.into_owned(),
+        }
+    }
+
+    #[test]
+    fn pin_files_are_read_per_format() {
+        let pin = |tool: &str, version: &str| (tool.to_string(), version.to_string());
+
+        assert_eq!(read_pin_file(".nvmrc", "v20.11.0\n"), vec![pin("node", "v20.11.0")]);
+        assert_eq!(
+            read_pin_file(".python-version", "3.12.1\n3.11\n"),
+            vec![pin("python", "3.12.1"), pin("python", "3.11")]
+        );
+        assert_eq!(read_pin_file(".ruby-version", "ruby-3.3.0\n"), vec![pin("ruby", "3.3.0")]);
+        assert_eq!(
+            read_pin_file(".tool-versions", "# pinned\nnodejs 18.19.0 20.11.0\npython 3.12.1\n"),
+            vec![pin("node", "18.19.0"), pin("node", "20.11.0"), pin("python", "3.12.1")]
+        );
+        assert_eq!(
+            read_pin_file(
+                "mise.toml",
+                "min_version = \"2026.2.19\"\n\n[tools]\nnode = \"22\"\npython = [\"3.12\", \
+                 \"3.11\"]\nruby = { version = \"3.3\" }\n\"cargo:cargo-tarpaulin\" = \"0.35.2\"\n"
+            ),
+            vec![
+                pin("cargo-cargo-tarpaulin", "0.35.2"),
+                pin("node", "22"),
+                pin("python", "3.12"),
+                pin("python", "3.11"),
+                pin("ruby", "3.3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unreferenced_keeps_pinned_newest_and_unparsed_versions() {
+        let installations = vec![
+            installation("/nvm/versions/node/v16.20.2", "node"),
+            installation("/nvm/versions/node/v20.11.0", "node"),
+            installation("/nvm/versions/node/v22.2.0", "node"),
+            installation("/mise/installs/node/20.11.0", "node"),
+            installation("/mise/installs/node/21.0.0", "node"),
+            installation("/pyenv/versions/3.1.4", "python"),
+            installation("/pyenv/versions/3.12.1", "python"),
+            installation("/pyenv/versions/3.13.0", "python"),
+            installation("/pyenv/versions/miniconda3-latest", "python"),
+        ];
+        let pins = ResolvedPins {
+            versions: vec![
+                ("node".to_string(), "20".to_string()),
+                ("python".to_string(), "3.12".to_string()),
+            ],
+            unresolved_tools: Vec::new(),
+        };
+
+        assert_eq!(
+            unreferenced(installations.clone(), &pins),
+            vec![
+                PathBuf::from("/nvm/versions/node/v16.20.2"),
+                PathBuf::from("/pyenv/versions/3.1.4")
+            ]
+        );
+
+        let unresolved = ResolvedPins { unresolved_tools: vec!["node".to_string()], ..pins };
+        assert_eq!(
+            unreferenced(installations, &unresolved),
+            vec![PathBuf::from("/pyenv/versions/3.1.4")]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolve_pins_follows_symlink_and_nvm_aliases() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let pyenv = temp.child("pyenv/versions");
+        pyenv.child("3.10.0/bin").create_dir_all().expect("version exists");
+        pyenv.child("3.11.4/envs/myenv/bin").create_dir_all().expect("virtualenv exists");
+        std::os::unix::fs::symlink(
+            pyenv.path().join("3.11.4/envs/myenv"),
+            pyenv.path().join("myenv"),
+        )
+        .expect("alias exists");
+        let nvm = temp.child("nvm");
+        nvm.child("versions/node/v20.11.0").create_dir_all().expect("version exists");
+        nvm.child("alias/lts/*").write_str("lts/iron\n").expect("alias exists");
+        nvm.child("alias/lts/iron").write_str("v20.11.0\n").expect("alias exists");
+
+        let tool_dirs = vec![
+            (pyenv.path().to_path_buf(), "python".to_string()),
+            (nvm.path().join("versions/node"), "node".to_string()),
+        ];
+        let installations = RuntimesTarget::installations(&tool_dirs);
+        let pin = |tool: &str, version: &str| (tool.to_string(), version.to_string());
+        let pins = [
+            pin("python", "myenv"),
+            pin("python", "system"),
+            pin("node", "lts/*"),
+            pin("ruby", "3.3"),
+            pin("ruby", "jruby-dev"),
+        ];
+
+        assert_eq!(
+            resolve_pins(&pins, &installations, &tool_dirs, Some(nvm.path())),
+            ResolvedPins {
+                versions: vec![
+                    pin("python", "3.11.4"),
+                    pin("node", "v20.11.0"),
+                    pin("ruby", "3.3")
+                ],
+                unresolved_tools: vec!["ruby".to_string()],
+            }
+        );
+    }
+}
@@ -1,18 +1,45 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use walkdir::WalkDir;
+use dirs_next as dirs;
 
 use crate::error::AppError;
 
 use super::category::Category;
 use super::item::CleanupItem;
+use super::project;
 use super::target::{CleanupTarget, ScanScope};
 
-pub struct RustTarget;
+pub struct RustTarget {
+    current: bool,
+}
 
 impl RustTarget {
-    pub fn new() -> Self {
-        Self
+    pub fn new(current: bool) -> Self {
+        Self { current }
+    }
+
+    /// Cargo's downloaded `.crate` archives, their extracted sources and git checkouts (under
+    /// `CARGO_HOME`, else `~/.cargo`), plus sccache's local cache. Cargo re-extracts sources from
+    /// the archives and re-creates checkouts from `git/db`, which is kept.
+    fn global_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let cargo_home = project::env_dir("CARGO_HOME")
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+        if let Some(cargo_home) = cargo_home {
+            paths.push(cargo_home.join("registry/cache"));
+            paths.push(cargo_home.join("registry/src"));
+            paths.push(cargo_home.join("git/checkouts"));
+        }
+
+        match project::env_dir("SCCACHE_DIR") {
+            Some(sccache) => paths.push(sccache),
+            None => paths.extend(
+                dirs::cache_dir()
+                    .iter()
+                    .flat_map(|cache| [cache.join("sccache"), cache.join("Mozilla.sccache")]),
+            ),
+        }
+        paths
     }
 
     fn is_rust_target_dir(path: &Path) -> bool {
@@ -28,7 +55,7 @@ impl RustTarget {
                 continue;
             }
 
-            let mut walker = WalkDir::new(root).max_depth(10).into_iter();
+            let mut walker = scope.walk(root);
             while let Some(entry) = walker.next() {
                 let entry = match entry {
                     Ok(entry) => entry,
@@ -51,31 +78,37 @@ impl RustTarget {
     }
 }
 
-impl Default for RustTarget {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 impl CleanupTarget for RustTarget {
     fn category(&self) -> Category {
         Category::Rust
     }
 
     fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
-        Ok(self
+        let mut items: Vec<_> = self
             .collect_targets(scope)
             .into_iter()
             .map(|path| CleanupItem::directory(Category::Rust, path, 0))
-            .collect())
+            .collect();
+        if !self.current {
+            items.extend(project::global_items(Category::Rust, &Self::global_paths()));
+        }
+        Ok(items)
     }
 
     fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
+        let mut targets = Vec::new();
         let count = self.collect_targets(scope).len();
-        if count == 0 {
-            return Ok(Vec::new());
+        if count > 0 {
+            targets.push(project::location_count("target", count));
+        }
+        if !self.current {
+            targets.extend(project::list_global(&Self::global_paths()));
         }
+        Ok(targets)
+    }
 
-        Ok(vec![format!("target ({} location{} found)", count, if count == 1 { "" } else { "s" })])
+    fn owns(&self, path: &Path) -> bool {
+        Self::is_rust_target_dir(path)
+            || Self::global_paths().iter().any(|candidate| candidate == path)
     }
 }
@@ -0,0 +1,244 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use dirs_next as dirs;
+
+use crate::error::AppError;
+
+use super::category::Category;
+use super::item::CleanupItem;
+use super::project;
+use super::target::{CleanupTarget, ScanScope};
+
+/// Files that pin a project to a toolchain, either a bare channel name (legacy `rust-toolchain`)
+/// or TOML with `[toolchain] channel = "…"`.
+const PIN_FILES: &[&str] = &["rust-toolchain", "rust-toolchain.toml"];
+
+/// `RUSTUP_HOME`, else `~/.rustup`.
+fn rustup_home() -> Option<PathBuf> {
+    project::env_dir("RUSTUP_HOME").or_else(|| dirs::home_dir().map(|home| home.join(".rustup")))
+}
+
+fn toolchains_dir() -> Option<PathBuf> {
+    rustup_home().map(|home| home.join("toolchains"))
+}
+
+/// The channel a pin file names, such as `1.90.0` or `nightly-2024-05-01`.
+fn read_pin(contents: &str) -> Option<String> {
+    let channel = match contents.parse::<toml::Table>() {
+        Ok(table) => table.get("toolchain")?.get("channel")?.as_str()?.trim().to_string(),
+        Err(_) => contents.lines().map(str::trim).find(|line| !line.is_empty())?.to_string(),
+    };
+    (!channel.is_empty()).then_some(channel)
+}
+
+/// The default toolchain and per-directory overrides from rustup's `settings.toml`.
+fn read_settings(contents: &str) -> Vec<String> {
+    let Ok(table) = contents.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let default = table.get("default_toolchain").and_then(|value| value.as_str());
+    let overrides = table
+        .get("overrides")
+        .and_then(|value| value.as_table())
+        .into_iter()
+        .flat_map(|overrides| overrides.values().filter_map(|value| value.as_str()));
+    default.into_iter().chain(overrides).map(str::to_string).collect()
+}
+
+/// Whether the installed toolchain `name` (`<channel>[-<date>]-<host>`) is the one `pin` selects.
+/// A pin without a host matches only when the rest is the host triple, so `nightly` does not
+/// keep every dated nightly and `1.90` does not keep `1.90.0`.
+fn is_pinned_by(name: &str, pin: &str) -> bool {
+    name == pin
+        || name
+            .strip_prefix(pin)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|host| host.starts_with(|c: char| c.is_ascii_alphabetic()))
+}
+
+/// Installed toolchains that no pin selects. Linked toolchains (symlinks) belong to the user and
+/// are never reported.
+fn unpinned_toolchains(toolchains: &Path, pins: &[String]) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(toolchains) else {
+        return Vec::new();
+    };
+    let mut unpinned: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            !pins.iter().any(|pin| is_pinned_by(&name, pin))
+        })
+        .map(|entry| entry.path())
+        .collect();
+    unpinned.sort();
+    unpinned
+}
+
+/// Pins from every `rust-toolchain` file under the scan roots, plus rustup's configured default
+/// and overrides.
+fn pins(scope: &ScanScope, rustup_home: &Path) -> Vec<String> {
+    let mut pins = fs::read_to_string(rustup_home.join("settings.toml"))
+        .map(|contents| read_settings(&contents))
+        .unwrap_or_default();
+
+    for root in scope.roots() {
+        if !root.exists() {
+            continue;
+        }
+
+        for entry in scope.walk(root).flatten() {
+            if entry.file_type().is_file()
+                && PIN_FILES.iter().any(|name| entry.file_name() == *name)
+                && let Ok(contents) = fs::read_to_string(entry.path())
+            {
+                pins.extend(read_pin(&contents));
+            }
+        }
+    }
+
+    pins
+}
+
+/// Installed toolchains that neither a project under the scan roots nor rustup's settings use.
+pub fn unused_toolchains(scope: &ScanScope) -> Vec<PathBuf> {
+    let Some(rustup_home) = rustup_home() else {
+        return Vec::new();
+    };
+    unpinned_toolchains(&rustup_home.join("toolchains"), &pins(scope, &rustup_home))
+}
+
+/// The toolchain name when `path` is an installed toolchain directory.
+pub fn toolchain_name(path: &Path) -> Option<String> {
+    let toolchains = toolchains_dir()?;
+    let parent = path.parent()?;
+    let in_toolchains = parent == toolchains
+        || fs::canonicalize(&toolchains).is_ok_and(|toolchains| parent == toolchains);
+    if !in_toolchains {
+        return None;
+    }
+    path.file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+/// Installed toolchains that nothing pins. Removing one breaks any project outside the scan roots
+/// that uses it, so the category is opt-in: it runs only when named with `--type` or in
+/// `scan.categories`, never by default or with `--all`.
+pub struct RustupTarget {
+    /// The scope whose pins protect toolchains when a saved plan is re-checked.
+    scope: ScanScope,
+}
+
+impl RustupTarget {
+    pub fn new(scope: ScanScope) -> Self {
+        Self { scope }
+    }
+}
+
+impl CleanupTarget for RustupTarget {
+    fn category(&self) -> Category {
+        Category::Rustup
+    }
+
+    fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError> {
+        Ok(project::global_items(Category::Rustup, &unused_toolchains(scope)))
+    }
+
+    fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError> {
+        Ok(unused_toolchains(scope)
+            .iter()
+            .map(|path| format!("{} (not pinned)", path.display()))
+            .collect())
+    }
+
+    /// Re-reads the pins, so a toolchain pinned after a plan was saved is no longer claimed.
+    fn owns(&self, path: &Path) -> bool {
+        unused_toolchains(&self.scope).iter().any(|candidate| candidate == path)
+    }
+}
+
+/// Runs `rustup toolchain uninstall`, so rustup's own bookkeeping stays consistent. Returns
+/// `false` when rustup is not installed and the caller should remove the directory itself.
+pub fn uninstall(name: &str, verbose: bool) -> Result<bool, AppError> {
+    let args = ["toolchain", "uninstall", name];
+    if verbose {
+        println!("$ rustup {}", args.join(" "));
+    }
+
+    let output = if verbose { Stdio::inherit } else { Stdio::null };
+    let status = Command::new("rustup").args(args).stdout(output()).stderr(output()).status();
+    match status {
+        Ok(status) if status.success() => Ok(true),
+        Ok(status) => Err(io::Error::other(format!(
+            "rustup {} failed with status {}",
+            args.join(" "),
+            status
+        ))
+        .into()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(AppError::Io(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use assert_fs::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn pins_read_toml_and_legacy_files() {
+        assert_eq!(
+            read_pin("[toolchain]\nchannel = \"1.90.0\"\nprofile = \"default\"\n").as_deref(),
+            Some("1.90.0")
+        );
+        assert_eq!(read_pin("\nnightly-2023-01-01\n").as_deref(), Some("nightly-2023-01-01"));
+        assert_eq!(read_pin("[toolchain]\npath = \"/opt/rust\"\n"), None);
+        assert_eq!(
+            read_settings(
+                "default_toolchain = \"stable-x86_64-unknown-linux-gnu\"\n\n[overrides]\n\
+                 \"/work/legacy\" = \"1.70.0-x86_64-unknown-linux-gnu\"\n"
+            ),
+            vec!["stable-x86_64-unknown-linux-gnu", "1.70.0-x86_64-unknown-linux-gnu"]
+        );
+    }
+
+    #[test]
+    fn unpinned_toolchains_skip_pinned_default_and_linked_toolchains() {
+        let temp = TempDir::new().expect("temp directory is created");
+        for name in [
+            "stable-x86_64-unknown-linux-gnu",
+            "1.90.0-x86_64-unknown-linux-gnu",
+            "1.90-x86_64-unknown-linux-gnu",
+            "nightly-x86_64-unknown-linux-gnu",
+            "nightly-2023-01-01-x86_64-unknown-linux-gnu",
+            "nightly-2024-05-01-aarch64-apple-darwin",
+        ] {
+            temp.child(name).child("bin").create_dir_all().expect("toolchain exists");
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(
+            temp.path().join("stable-x86_64-unknown-linux-gnu"),
+            temp.path().join("dev"),
+        )
+        .expect("linked toolchain exists");
+
+        let pins = [
+            "stable-x86_64-unknown-linux-gnu".to_string(),
+            "1.90.0".to_string(),
+            "nightly-2024-05-01".to_string(),
+        ];
+
+        assert_eq!(
+            unpinned_toolchains(temp.path(), &pins),
+            vec![
+                temp.path().join("1.90-x86_64-unknown-linux-gnu"),
+                temp.path().join("nightly-2023-01-01-x86_64-unknown-linux-gnu"),
+                temp.path().join("nightly-x86_64-unknown-linux-gnu"),
+            ]
+        );
+    }
+}
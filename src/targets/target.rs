@@ -1,20 +1,52 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use walkdir::{DirEntry, FilterEntry, WalkDir};
+
+use crate::config::DEFAULT_MAX_DEPTH;
 use crate::error::AppError;
+use crate::fs::exclude::{ExcludeRules, LocalRules};
+use crate::state::state_dir;
 
 use super::category::Category;
-use super::item::CleanupItem;
+use super::item::{AgeFilter, CleanupItem};
 
 #[derive(Debug, Clone)]
 pub struct ScanScope {
     roots: Vec<PathBuf>,
     current: bool,
     verbose: bool,
+    max_depth: usize,
+    excludes: ExcludeRules,
+    age: AgeFilter,
+    state_dir: Option<PathBuf>,
 }
 
 impl ScanScope {
     pub fn new(roots: Vec<PathBuf>, current: bool, verbose: bool) -> Self {
-        Self { roots, current, verbose }
+        Self {
+            roots,
+            current,
+            verbose,
+            max_depth: DEFAULT_MAX_DEPTH,
+            excludes: ExcludeRules::default(),
+            age: AgeFilter::default(),
+            state_dir: state_dir().ok(),
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_excludes(mut self, excludes: ExcludeRules) -> Self {
+        self.excludes = excludes;
+        self
+    }
+
+    pub fn with_age_filter(mut self, age: AgeFilter) -> Self {
+        self.age = age;
+        self
     }
 
     pub fn roots(&self) -> &[PathBuf] {
@@ -28,10 +60,44 @@ impl ScanScope {
     pub fn verbose(&self) -> bool {
         self.verbose
     }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn age_filter(&self) -> AgeFilter {
+        self.age
+    }
+
+    /// Whether `path` is excluded from scanning, either by `--exclude`/config patterns or
+    /// because it is prf's own state.
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        self.state_dir.as_ref().is_some_and(|state_dir| absolute.starts_with(state_dir))
+            || self.excludes.is_excluded(&absolute, is_dir)
+    }
+
+    /// Walks `root` up to the configured depth, pruning excluded subtrees (including those
+    /// matched by `.prfignore` files below `root`) before descending.
+    pub fn walk<'a>(
+        &'a self,
+        root: &Path,
+    ) -> FilterEntry<walkdir::IntoIter, impl FnMut(&DirEntry) -> bool + 'a> {
+        let absolute_root = std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf());
+        let mut local = LocalRules::default();
+
+        WalkDir::new(root).max_depth(self.max_depth).into_iter().filter_entry(move |entry| {
+            let path = std::path::absolute(entry.path()).unwrap_or_else(|_| entry.path().into());
+            let is_dir = entry.file_type().is_dir();
+            !self.is_excluded(&path, is_dir) && !local.is_excluded(&absolute_root, &path, is_dir)
+        })
+    }
 }
 
 pub trait CleanupTarget: Send + Sync {
     fn category(&self) -> Category;
     fn discover(&self, scope: &ScanScope) -> Result<Vec<CleanupItem>, AppError>;
     fn list(&self, scope: &ScanScope) -> Result<Vec<String>, AppError>;
+    /// Whether `path` still satisfies the rule this target uses to discover items.
+    fn owns(&self, path: &Path) -> bool;
 }
@@ -2,21 +2,39 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
 use dirs_next as dirs;
-use walkdir::WalkDir;
 
 use crate::error::AppError;
 
 use super::category::Category;
-use super::item::{CleanupItem, ItemKind};
+use super::dart::FLUTTER_PLATFORM_MARKERS;
+use super::item::CleanupItem;
+use super::project::{ProjectRule, RuleTable};
 use super::target::{CleanupTarget, ScanScope};
 
+/// Dependency manager outputs, found next to the manifest that produced them.
+const DEPENDENCY_RULES: &[ProjectRule] = &[
+    ProjectRule::new(".build", &["Package.swift"]),
+    ProjectRule::new(".swiftpm", &["Package.swift"]),
+    ProjectRule::new("Pods", &["Podfile"]).unless(FLUTTER_PLATFORM_MARKERS),
+    ProjectRule::new("Carthage/Build", &["Cartfile"]),
+    ProjectRule::new("Carthage/Checkouts", &["Cartfile"]),
+];
+
+/// `--list` labels for projects of each dependency manager, keyed by manifest.
+const DEPENDENCY_MANAGERS: &[(&str, &str)] = &[
+    ("Package.swift", "SwiftPM Projects (.build, .swiftpm)"),
+    ("Podfile", "CocoaPods Projects (Pods)"),
+    ("Cartfile", "Carthage Projects (Carthage/Build, Carthage/Checkouts)"),
+];
+
 pub struct XcodeTarget {
+    dependency_rules: RuleTable<ProjectRule>,
     current: bool,
 }
 
 impl XcodeTarget {
     pub fn new(current: bool) -> Self {
-        Self { current }
+        Self { dependency_rules: RuleTable::new(DEPENDENCY_RULES), current }
     }
 
     fn global_safe_paths() -> Vec<PathBuf> {
@@ -29,6 +47,7 @@ impl XcodeTarget {
             paths.push(lib.join("Developer/Xcode/DocumentationIndex"));
             paths.push(lib.join("Developer/Xcode/UserData/Previews"));
             paths.push(lib.join("Caches/org.swift.swiftpm"));
+            paths.push(lib.join("Caches/CocoaPods"));
             paths.push(lib.join("org.swift.swiftpm"));
             paths.push(lib.join("Developer/CoreSimulator/Caches"));
         }
@@ -36,21 +55,35 @@ impl XcodeTarget {
     }
 
     fn add_path(&self, path: &Path, items: &mut Vec<CleanupItem>) {
-        let kind = if path.is_file() { ItemKind::File } else { ItemKind::Directory };
-        items.push(CleanupItem {
-            category: Category::Xcode,
-            path: path.to_path_buf(),
-            size: 0,
-            kind,
-        });
+        items.push(Self::item(path));
+    }
+
+    fn item(path: &Path) -> CleanupItem {
+        if path.is_file() {
+            CleanupItem::file(Category::Xcode, path.to_path_buf(), 0)
+        } else {
+            CleanupItem::directory(Category::Xcode, path.to_path_buf(), 0)
+        }
     }
 
-    fn collect_swiftpm_artifacts(&self, parent: &Path, items: &mut Vec<CleanupItem>) {
-        const ARTIFACTS: &[&str] = &[".build", ".swiftpm"];
-        for artifact in ARTIFACTS {
-            let artifact_path = parent.join(artifact);
-            if artifact_path.exists() {
-                self.add_path(&artifact_path, items);
+    fn is_dependency_artifact(&self, path: &Path) -> bool {
+        self.dependency_rules.matches(path)
+    }
+
+    fn is_dependency_manifest(file_name: &str) -> bool {
+        DEPENDENCY_MANAGERS.iter().any(|(manifest, _)| *manifest == file_name)
+    }
+
+    fn collect_dependency_artifacts(
+        &self,
+        parent: &Path,
+        manifest: &str,
+        items: &mut Vec<CleanupItem>,
+    ) {
+        for rule in DEPENDENCY_RULES.iter().filter(|rule| rule.markers.contains(&manifest)) {
+            let artifact_path = parent.join(rule.path);
+            if artifact_path.exists() && self.is_dependency_artifact(&artifact_path) {
+                items.push(Self::item(&artifact_path).in_project(parent.to_path_buf()));
             }
         }
     }
@@ -67,14 +100,14 @@ impl XcodeTarget {
 
     fn scan_local_projects(&self, scope: &ScanScope) -> Vec<CleanupItem> {
         let mut items = Vec::new();
-        let mut processed_packages: HashSet<PathBuf> = HashSet::new();
+        let mut processed_manifests: HashSet<PathBuf> = HashSet::new();
 
         for root in scope.roots() {
             if !root.exists() {
                 continue;
             }
 
-            let mut walker = WalkDir::new(root).max_depth(10).into_iter();
+            let mut walker = scope.walk(root);
             while let Some(entry) = walker.next() {
                 let entry = match entry {
                     Ok(entry) => entry,
@@ -95,12 +128,18 @@ impl XcodeTarget {
                     continue;
                 }
 
+                // Artifacts are collected from their manifest; their contents need no walk.
+                if entry.file_type().is_dir() && self.is_dependency_artifact(path) {
+                    walker.skip_current_dir();
+                    continue;
+                }
+
                 if entry.file_type().is_file()
-                    && file_name == "Package.swift"
+                    && Self::is_dependency_manifest(&file_name)
                     && let Some(parent) = path.parent()
-                    && processed_packages.insert(parent.to_path_buf())
+                    && processed_manifests.insert(path.to_path_buf())
                 {
-                    self.collect_swiftpm_artifacts(parent, &mut items);
+                    self.collect_dependency_artifacts(parent, &file_name, &mut items);
                 }
             }
         }
@@ -121,14 +160,14 @@ impl XcodeTarget {
     fn list_local_targets(&self, scope: &ScanScope) -> Vec<String> {
         let mut targets = Vec::new();
         let mut derived_data = 0usize;
-        let mut swiftpm_projects = 0usize;
+        let mut manager_projects = [0usize; DEPENDENCY_MANAGERS.len()];
 
         for root in scope.roots() {
             if !root.exists() {
                 continue;
             }
 
-            let mut walker = WalkDir::new(root).max_depth(10).into_iter();
+            let mut walker = scope.walk(root);
             while let Some(entry) = walker.next() {
                 let entry = match entry {
                     Ok(entry) => entry,
@@ -144,8 +183,13 @@ impl XcodeTarget {
                 if entry.file_type().is_dir() && file_name == "DerivedData" {
                     derived_data += 1;
                     walker.skip_current_dir();
-                } else if entry.file_type().is_file() && file_name == "Package.swift" {
-                    swiftpm_projects += 1;
+                } else if entry.file_type().is_dir() && self.is_dependency_artifact(entry.path()) {
+                    walker.skip_current_dir();
+                } else if entry.file_type().is_file()
+                    && let Some(index) =
+                        DEPENDENCY_MANAGERS.iter().position(|(manifest, _)| *manifest == file_name)
+                {
+                    manager_projects[index] += 1;
                 }
             }
         }
@@ -158,12 +202,15 @@ impl XcodeTarget {
             ));
         }
 
-        if swiftpm_projects > 0 {
-            targets.push(format!(
-                "SwiftPM Projects (.build, .swiftpm) ({} location{} found)",
-                swiftpm_projects,
-                if swiftpm_projects == 1 { "" } else { "s" }
-            ));
+        for ((_, label), count) in DEPENDENCY_MANAGERS.iter().zip(manager_projects) {
+            if count > 0 {
+                targets.push(format!(
+                    "{} ({} location{} found)",
+                    label,
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ));
+            }
         }
 
         targets
@@ -192,6 +239,12 @@ impl CleanupTarget for XcodeTarget {
         }
         Ok(targets)
     }
+
+    fn owns(&self, path: &Path) -> bool {
+        path.file_name().is_some_and(|name| name == "DerivedData")
+            || self.is_dependency_artifact(path)
+            || Self::global_safe_paths().iter().any(|candidate| candidate == path)
+    }
 }
 
 #[cfg(test)]
@@ -296,6 +349,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn discover_detects_cocoapods_and_carthage_artifacts_next_to_manifests() {
+        let temp = TempDir::new().expect("temp directory is created");
+        let roots = temp.child("workspace");
+        roots.child("PodsApp/Podfile").write_str("platform :ios").expect("podfile exists");
+        roots.child("PodsApp/Pods/Alamofire/Source.swift").write_str("src").expect("pods exist");
+        roots.child("CartApp/Cartfile").write_str("github \"a/b\"").expect("cartfile exists");
+        roots.child("CartApp/Carthage/Build/B.framework").create_dir_all().expect("build exists");
+        roots.child("CartApp/Carthage/Checkouts/b").create_dir_all().expect("checkout exists");
+        roots.child("Stray/Pods/Leftover").create_dir_all().expect("stray pods exist");
+
+        let target = XcodeTarget::new(true);
+        let scope = ScanScope::new(vec![roots.path().to_path_buf()], true, false);
+        let mut paths: Vec<_> = target
+            .discover(&scope)
+            .expect("scan succeeds")
+            .into_iter()
+            .map(|item| item.path)
+            .collect();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                roots.path().join("CartApp/Carthage/Build"),
+                roots.path().join("CartApp/Carthage/Checkouts"),
+                roots.path().join("PodsApp/Pods"),
+            ]
+        );
+        assert!(target.owns(&roots.path().join("PodsApp/Pods")));
+        assert!(!target.owns(&roots.path().join("Stray/Pods")));
+
+        let listed = target.list(&scope).expect("list succeeds");
+        assert!(listed.contains(&"CocoaPods Projects (Pods) (1 location found)".to_string()));
+    }
+
     #[test]
     #[serial]
     fn discover_global_caches_respects_current_flag() {
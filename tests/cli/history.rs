@@ -0,0 +1,44 @@
+use crate::harness::TestContext;
+use predicates::prelude::*;
+
+#[test]
+fn history_lists_and_filters_recorded_deletions() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("workspace/node_modules/index.js", "console.log('cache');");
+
+    ctx.cli().arg("history").assert().success().stdout(predicate::str::contains("No deletions"));
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("-y")
+        .arg(ctx.home().join("workspace"))
+        .assert()
+        .success();
+
+    ctx.cli()
+        .arg("history")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deletion history"))
+        .stdout(predicate::str::contains("delete"))
+        .stdout(predicate::str::contains("~/workspace/node_modules"))
+        .stdout(predicate::str::contains("Total:"));
+
+    ctx.cli()
+        .arg("history")
+        .arg("--type")
+        .arg("python")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No deletions"));
+
+    ctx.cli()
+        .arg("history")
+        .arg("--until")
+        .arg("1d")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No deletions"));
+}
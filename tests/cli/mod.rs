@@ -1,4 +1,6 @@
 mod aliases;
 mod help_and_version;
+mod history;
+mod quarantine;
 mod run;
 mod scan;
@@ -0,0 +1,81 @@
+use crate::harness::TestContext;
+use predicates::prelude::*;
+
+#[test]
+fn run_quarantine_then_restore_by_path() {
+    let ctx = TestContext::new();
+    let cache = ctx.write_home_file("workspace/node_modules/index.js", "console.log('cache');");
+    let cache_dir = cache.parent().expect("cache file has parent").to_path_buf();
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--quarantine")
+        .arg("-y")
+        .arg(ctx.home().join("workspace"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Attempted to quarantine"));
+    assert!(!cache_dir.exists(), "cache directory should be quarantined");
+
+    ctx.cli()
+        .arg("restore")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Quarantined items"))
+        .stdout(predicate::str::contains("~/workspace/node_modules"));
+
+    ctx.cli()
+        .arg("restore")
+        .arg(&cache_dir)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored ~/workspace/node_modules"));
+    assert!(cache.exists(), "cache file should be restored");
+}
+
+#[test]
+fn purge_respects_older_than_and_frees_quarantine() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("workspace/__pycache__/foo.pyc", "cache");
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("python")
+        .arg("--quarantine")
+        .arg("-y")
+        .arg(ctx.home().join("workspace"))
+        .assert()
+        .success();
+
+    ctx.cli()
+        .arg("purge")
+        .arg("--older-than")
+        .arg("7d")
+        .arg("-y")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to purge."));
+
+    ctx.cli().arg("purge").arg("-y").assert().success().stdout(predicate::str::contains("Purged"));
+
+    ctx.cli()
+        .arg("restore")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Quarantine is empty."));
+}
+
+#[test]
+fn restore_unknown_entry_fails() {
+    let ctx = TestContext::new();
+
+    ctx.cli()
+        .arg("restore")
+        .arg("does-not-exist")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No quarantined item matches 'does-not-exist'"));
+}
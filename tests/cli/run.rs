@@ -37,3 +37,278 @@ fn run_interactive_accepts_selection() {
 
     assert!(!cache_dir.exists(), "cache directory should be deleted");
 }
+
+#[test]
+fn run_trash_moves_directories_into_trash() {
+    let ctx = TestContext::new();
+    ctx.set_env("XDG_DATA_HOME", ctx.home().join(".local/share"));
+    let cache = ctx.write_home_file("workspace/node_modules/index.js", "console.log('cache');");
+    let cache_dir = cache.parent().expect("cache file has parent").to_path_buf();
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--trash")
+        .arg("-y")
+        .arg(ctx.home().join("workspace"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Attempted to move"));
+
+    let trashed = if cfg!(target_os = "macos") {
+        ctx.home().join(".Trash/node_modules")
+    } else {
+        ctx.home().join(".local/share/Trash/files/node_modules")
+    };
+    assert!(!cache_dir.exists(), "cache directory should be moved away");
+    assert!(trashed.join("index.js").exists(), "cache directory should be in the trash");
+}
+
+#[test]
+fn run_applies_confirm_and_mode_from_config() {
+    let ctx = TestContext::new();
+    let cache = ctx.write_home_file("workspace/node_modules/index.js", "console.log('cache');");
+    let cache_dir = cache.parent().expect("cache file has parent").to_path_buf();
+    ctx.write_home_file(
+        ".config/prf/config.toml",
+        "[run]\nconfirm = false\nmode = \"quarantine\"\n",
+    );
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("nodejs")
+        .arg(ctx.home().join("workspace"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Attempted to quarantine"));
+
+    assert!(!cache_dir.exists(), "cache directory should be quarantined");
+
+    ctx.cli().arg("restore").arg(&cache_dir).assert().success();
+    ctx.write_home_file("workspace/node_modules/extra.js", "more");
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--delete")
+        .arg(ctx.home().join("workspace"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Attempted to delete"));
+    assert!(!cache_dir.exists(), "cache directory should be deleted");
+}
+
+#[test]
+fn run_plan_executes_saved_plan_and_skips_drifted_items() {
+    let ctx = TestContext::new();
+    let kept = ctx.write_home_file("a/node_modules/index.js", "console.log('a');");
+    let removed = ctx.write_home_file("b/node_modules/index.js", "console.log('b');");
+    let plan = ctx.home().join("plan.json");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--save-plan")
+        .arg(&plan)
+        .arg(ctx.home())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved deletion plan"));
+
+    std::fs::remove_dir_all(removed.parent().expect("cache has parent")).expect("drift is created");
+    let added = ctx.write_home_file("c/node_modules/index.js", "console.log('c');");
+
+    ctx.cli()
+        .arg("run")
+        .arg("--plan")
+        .arg(&plan)
+        .arg("-y")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Skipped 1 item(s)"))
+        .stdout(predicate::str::contains("no longer exists"))
+        .stdout(predicate::str::contains("Attempted to delete"));
+
+    assert!(!kept.exists(), "planned item should be deleted");
+    assert!(added.exists(), "items discovered after the plan was saved must not be touched");
+}
+
+#[cfg(unix)]
+#[test]
+fn run_uninstalls_unpinned_rustup_toolchains_through_rustup() {
+    let ctx = TestContext::new();
+    ctx.create_mock_command(
+        "rustup",
+        r#"#!/bin/sh
+echo "$@" >> "$HOME/rustup.log"
+rm -rf "$HOME/.rustup/toolchains/$3"
+"#,
+    );
+    ctx.write_home_file(
+        ".rustup/settings.toml",
+        "default_toolchain = \"stable-x86_64-unknown-linux-gnu\"\n",
+    );
+    for toolchain in [
+        "stable-x86_64-unknown-linux-gnu",
+        "nightly-2023-01-01-x86_64-unknown-linux-gnu",
+        "nightly-2024-05-01-x86_64-unknown-linux-gnu",
+    ] {
+        ctx.write_home_file(format!(".rustup/toolchains/{toolchain}/bin/rustc"), "rustc");
+    }
+    ctx.write_home_file(
+        "code/app/rust-toolchain.toml",
+        "[toolchain]\nchannel = \"nightly-2024-05-01\"\n",
+    );
+
+    // Toolchains are opt-in: neither the rust category nor `--all` touches them.
+    for selection in [["--type", "rust"], ["--all", "--delete"]] {
+        ctx.cli()
+            .arg("run")
+            .args(selection)
+            .arg("-y")
+            .arg(ctx.home().join("code"))
+            .assert()
+            .success();
+    }
+    assert!(!ctx.home().join("rustup.log").exists());
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("rustup")
+        .arg("-y")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Attempted to delete"));
+
+    let log = std::fs::read_to_string(ctx.home().join("rustup.log")).expect("rustup was run");
+    assert_eq!(log, "toolchain uninstall nightly-2023-01-01-x86_64-unknown-linux-gnu\n");
+    assert!(
+        !ctx.home().join(".rustup/toolchains/nightly-2023-01-01-x86_64-unknown-linux-gnu").exists()
+    );
+    assert!(
+        ctx.home().join(".rustup/toolchains/nightly-2024-05-01-x86_64-unknown-linux-gnu").exists()
+    );
+    assert!(ctx.home().join(".rustup/toolchains/stable-x86_64-unknown-linux-gnu").exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn run_plan_keeps_rustup_toolchains_pinned_after_the_plan_was_saved() {
+    let ctx = TestContext::new();
+    ctx.create_mock_command("rustup", "#!/bin/sh\necho \"$@\" >> \"$HOME/rustup.log\"\n");
+    let toolchain =
+        ctx.create_home_dir(".rustup/toolchains/nightly-2023-01-01-x86_64-unknown-linux-gnu");
+    let plan = ctx.home().join("plan.json");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("rustup")
+        .arg("--save-plan")
+        .arg(&plan)
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success();
+
+    ctx.write_home_file("code/legacy/rust-toolchain", "nightly-2023-01-01\n");
+
+    ctx.cli()
+        .arg("run")
+        .arg("--plan")
+        .arg(&plan)
+        .arg("-y")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no longer matches its category rule"));
+
+    assert!(!ctx.home().join("rustup.log").exists());
+    assert!(toolchain.exists());
+}
+
+#[test]
+fn run_plan_keeps_android_sdk_packages_referenced_after_the_plan_was_saved() {
+    let ctx = TestContext::new();
+    ctx.set_env("ANDROID_HOME", ctx.home().join("sdk"));
+    ctx.set_env("ANDROID_USER_HOME", ctx.home().join(".android"));
+    ctx.set_env("ANDROID_AVD_HOME", ctx.home().join(".android/avd"));
+    let package = ctx.write_home_file("sdk/build-tools/30.0.3/package.xml", "<package/>");
+    ctx.write_home_file("sdk/build-tools/34.0.0/package.xml", "<package/>");
+    ctx.create_home_dir("code");
+    let plan = ctx.home().join("plan.json");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("android")
+        .arg("--save-plan")
+        .arg(&plan)
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success();
+
+    ctx.write_home_file(
+        "code/legacy/app/build.gradle",
+        "android {\n    buildToolsVersion \"30.0.3\"\n}\n",
+    );
+
+    ctx.cli()
+        .arg("run")
+        .arg("--plan")
+        .arg(&plan)
+        .arg("-y")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no longer matches its category rule"));
+
+    assert!(package.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn run_removes_unpinned_runtime_versions_and_rehashes_shims() {
+    let ctx = TestContext::new();
+    ctx.create_mock_command("pyenv", "#!/bin/sh\necho \"$@\" >> \"$HOME/pyenv.log\"\n");
+    let versions = ctx.home().join(".pyenv/versions");
+    for version in ["3.10.0", "3.11.4/envs/myenv", "3.12.1"] {
+        ctx.write_home_file(format!(".pyenv/versions/{version}/bin/python"), "#!");
+    }
+    std::os::unix::fs::symlink(versions.join("3.11.4/envs/myenv"), versions.join("myenv"))
+        .expect("virtualenv alias exists");
+    ctx.write_home_file("code/api/.python-version", "myenv\n");
+
+    // Runtimes are opt-in, so `--all` leaves every version in place.
+    ctx.cli()
+        .arg("run")
+        .arg("--all")
+        .arg("--delete")
+        .arg("-y")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success();
+    assert!(versions.join("3.10.0").exists());
+
+    ctx.cli()
+        .arg("run")
+        .arg("--type")
+        .arg("runtimes")
+        .arg("-y")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Attempted to delete"));
+
+    assert!(!versions.join("3.10.0").exists());
+    assert!(
+        versions.join("3.11.4/envs/myenv").exists(),
+        "the virtualenv pin keeps its interpreter"
+    );
+    assert!(versions.join("3.12.1").exists());
+    let log = std::fs::read_to_string(ctx.home().join("pyenv.log")).expect("pyenv was run");
+    assert_eq!(log, "rehash\n");
+}
@@ -1,5 +1,6 @@
 use crate::harness::TestContext;
 use predicates::prelude::*;
+use std::time::Duration;
 
 #[test]
 fn scan_python_verbose_lists_targets() {
@@ -55,3 +56,491 @@ exit 0
         .stdout(predicate::str::contains("Unused images"))
         .stdout(predicate::str::contains("Build cache"));
 }
+
+#[test]
+fn scan_format_json_reports_raw_sizes() {
+    let ctx = TestContext::new();
+    let cache = ctx.write_home_file("project/__pycache__/foo.pyc", "cache");
+
+    let output = ctx
+        .cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("python")
+        .arg("--format")
+        .arg("json")
+        .arg(ctx.home())
+        .output()
+        .expect("scan runs");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    assert_eq!(json["total_size"], 5);
+    let python = &json["categories"][0];
+    assert_eq!(python["category"], "python");
+    assert_eq!(python["total_size"], 5);
+    assert_eq!(python["items"][0]["kind"], "directory");
+    assert_eq!(
+        python["items"][0]["path"],
+        cache.parent().expect("cache has parent").to_string_lossy().as_ref()
+    );
+}
+
+#[test]
+fn scan_format_ndjson_emits_one_item_per_line() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("a/node_modules/index.js", "a");
+    ctx.write_home_file("b/node_modules/index.js", "bb");
+
+    let output = ctx
+        .cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--format")
+        .arg("ndjson")
+        .arg(ctx.home())
+        .output()
+        .expect("scan runs");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("stdout is UTF-8");
+    let items: Vec<serde_json::Value> =
+        stdout.lines().map(|line| serde_json::from_str(line).expect("each line is JSON")).collect();
+    assert_eq!(items.len(), 2);
+    assert!(items.iter().all(|item| item["category"] == "nodejs"));
+    assert_eq!(items.iter().map(|item| item["size"].as_u64().unwrap_or(0)).sum::<u64>(), 3);
+}
+
+#[test]
+fn scan_uses_roots_categories_and_excludes_from_config() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("src/app/node_modules/index.js", "app");
+    ctx.write_home_file("src/app/__pycache__/foo.pyc", "cache");
+    ctx.write_home_file("src/vendored/node_modules/index.js", "keep");
+    ctx.write_home_file("Desktop/other/node_modules/index.js", "desk");
+    ctx.write_home_file(
+        ".config/prf/config.toml",
+        "[scan]\nroots = [\"~/src\"]\ncategories = [\"nodejs\"]\nexclude = [\"~/src/vendored\"]\n",
+    );
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--verbose")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("~/src/app/node_modules"))
+        .stdout(predicate::str::contains("__pycache__").not())
+        .stdout(predicate::str::contains("vendored").not())
+        .stdout(predicate::str::contains("Desktop").not());
+}
+
+#[test]
+fn scan_rejects_invalid_config_file() {
+    let ctx = TestContext::new();
+    let config = ctx.write_home_file("prf.toml", "[scan]\nroot = []\n");
+    ctx.set_env("PRF_CONFIG", &config);
+
+    ctx.cli()
+        .arg("scan")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid configuration"));
+}
+
+#[test]
+fn scan_supports_custom_categories_from_config() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("tool/gen.yaml", "version: 1");
+    ctx.write_home_file("tool/.gen-cache/blob", "cache");
+    ctx.write_home_file("unrelated/.gen-cache/blob", "cache");
+    let config = ctx.write_home_file(
+        "prf.toml",
+        "[[category]]\nname = \"gen\"\ndisplay_name = \"Generated\"\nnames = [\".gen-cache\", \"out-bundle\"]\nmarker = \"gen.yaml\"\n",
+    );
+    ctx.set_env("PRF_CONFIG", &config);
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("gen")
+        .arg("--verbose")
+        .arg(ctx.home())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Generated"))
+        .stdout(predicate::str::contains("~/tool/.gen-cache"))
+        .stdout(predicate::str::contains("unrelated").not());
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--list")
+        .arg(ctx.home())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Generated"))
+        .stdout(predicate::str::contains(".gen-cache (1 location found)"));
+}
+
+#[test]
+fn scan_skips_paths_matched_by_prfignore_and_exclude_flags() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("vendored/.prfignore", "node_modules\n");
+    ctx.write_home_file("vendored/node_modules/index.js", "keep");
+    ctx.write_home_file("app/node_modules/index.js", "drop");
+    ctx.write_home_file("app/.venv/bin/python", "#!");
+    ctx.write_home_file("lib/.venv/bin/python", "#!");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--type")
+        .arg("python")
+        .arg("--exclude")
+        .arg("/**/app/.venv")
+        .arg("--verbose")
+        .arg(ctx.home())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("~/app/node_modules"))
+        .stdout(predicate::str::contains("~/lib/.venv"))
+        .stdout(predicate::str::contains("vendored").not())
+        .stdout(predicate::str::contains("~/app/.venv").not());
+}
+
+#[test]
+fn scan_older_than_keeps_only_untouched_projects() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("stale/Cargo.toml", "[package]");
+    ctx.write_home_file("stale/target/debug/app", "binary");
+    ctx.write_home_file("fresh/Cargo.toml", "[package]");
+    ctx.write_home_file("fresh/target/debug/app", "binary");
+    ctx.age_home_path("stale", Duration::from_secs(40 * 86_400));
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("rust")
+        .arg("--older-than")
+        .arg("30d")
+        .arg("--verbose")
+        .arg(ctx.home())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("~/stale/target"))
+        .stdout(predicate::str::contains("5w old"))
+        .stdout(predicate::str::contains("fresh").not());
+
+    let output = ctx
+        .cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("rust")
+        .arg("--newer-than")
+        .arg("1d")
+        .arg("--format")
+        .arg("json")
+        .arg(ctx.home())
+        .output()
+        .expect("scan runs");
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    let items = json["categories"][0]["items"].as_array().expect("items are listed");
+    assert_eq!(items.len(), 1);
+    assert!(items[0]["path"].as_str().is_some_and(|path| path.ends_with("fresh/target")));
+    assert!(items[0]["modified"].is_string());
+}
+
+#[test]
+fn scan_go_reports_caches_from_go_env() {
+    let ctx = TestContext::new();
+    let cache = ctx.write_home_file("caches/go-build/00/entry", "cache");
+    let modcache = ctx.write_home_file("gopath/pkg/mod/example.com/lib@v1/lib.go", "package lib");
+    ctx.create_mock_command(
+        "go",
+        &format!(
+            "#!/bin/sh\nif [ \"$1\" = \"env\" ]; then\n  echo {}\n  echo {}\nfi\n",
+            ctx.home().join("caches/go-build").display(),
+            ctx.home().join("gopath/pkg/mod").display()
+        ),
+    );
+    assert!(cache.exists() && modcache.exists());
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("go")
+        .arg("--verbose")
+        .arg(ctx.work_dir())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Go"))
+        .stdout(predicate::str::contains("~/caches/go-build"))
+        .stdout(predicate::str::contains("~/gopath/pkg/mod"));
+}
+
+#[test]
+fn scan_jvm_reports_maven_targets_and_build_caches() {
+    let ctx = TestContext::new();
+    ctx.write_home_file(".gradle/caches/modules-2/files.bin", "cache");
+    ctx.write_home_file(".m2/repository/org/lib/1.0/lib-1.0.jar", "jar");
+    ctx.write_home_file("code/api/pom.xml", "<project/>");
+    ctx.write_home_file("code/api/target/classes/App.class", "class");
+    ctx.write_home_file("code/bridge/pom.xml", "<project/>");
+    ctx.write_home_file("code/bridge/Cargo.toml", "[package]");
+    ctx.write_home_file("code/bridge/target/debug/bridge", "bin");
+
+    ctx.cli()
+        .env_remove("GRADLE_USER_HOME")
+        .arg("scan")
+        .arg("--type")
+        .arg("jvm")
+        .arg("--verbose")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("JVM"))
+        .stdout(predicate::str::contains("~/code/api/target"))
+        .stdout(predicate::str::contains("bridge/target").not())
+        .stdout(predicate::str::contains("~/.gradle/caches"))
+        .stdout(predicate::str::contains("~/.m2/repository"));
+}
+
+#[test]
+fn scan_android_reports_unreferenced_sdk_packages_and_emulator_snapshots() {
+    let ctx = TestContext::new();
+    ctx.set_env("ANDROID_HOME", ctx.home().join("sdk"));
+    ctx.set_env("ANDROID_USER_HOME", ctx.home().join(".android"));
+    ctx.set_env("ANDROID_AVD_HOME", ctx.home().join(".android/avd"));
+    for package in ["build-tools/30.0.3", "build-tools/34.0.0", "system-images/android-31"] {
+        ctx.write_home_file(format!("sdk/{package}/package.xml"), "<package/>");
+    }
+    ctx.write_home_file("sdk/system-images/android-34/package.xml", "<package/>");
+    ctx.write_home_file(
+        ".android/avd/Pixel.avd/config.ini",
+        "image.sysdir.1=system-images/android-34/google_apis/arm64-v8a/\n",
+    );
+    ctx.write_home_file(".android/avd/Pixel.avd/snapshots/default_boot/ram.bin", "ram");
+    ctx.write_home_file("code/app/settings.gradle.kts", "");
+    ctx.write_home_file("code/app/app/build.gradle.kts", "android {\n    compileSdk = 34\n}\n");
+    ctx.write_home_file("code/app/app/src/main/AndroidManifest.xml", "<manifest/>");
+    ctx.write_home_file("code/app/app/build/outputs/app.apk", "apk");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("android")
+        .arg("--verbose")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Android"))
+        .stdout(predicate::str::contains("~/code/app/app/build"))
+        .stdout(predicate::str::contains("~/.android/avd/Pixel.avd/snapshots"))
+        .stdout(predicate::str::contains("~/sdk/build-tools/30.0.3"))
+        .stdout(predicate::str::contains("~/sdk/system-images/android-31"))
+        .stdout(predicate::str::contains("build-tools/34.0.0").not())
+        .stdout(predicate::str::contains("android-34").not());
+}
+
+#[test]
+fn scan_dart_reports_flutter_outputs_and_pub_cache() {
+    let ctx = TestContext::new();
+    ctx.set_env("PUB_CACHE", ctx.home().join(".pub-cache"));
+    ctx.set_env("FLUTTER_ROOT", ctx.home().join("flutter"));
+    ctx.write_home_file(".pub-cache/hosted/pub.dev/http-1.2.0/pubspec.yaml", "name: http");
+    ctx.write_home_file("flutter/bin/cache/artifacts/engine/ios/Flutter.xcframework", "engine");
+    ctx.write_home_file("code/app/pubspec.yaml", "name: app");
+    ctx.write_home_file("code/app/.dart_tool/package_config.json", "{}");
+    ctx.write_home_file("code/app/ios/Podfile", "platform :ios");
+    ctx.write_home_file("code/app/ios/Pods/Manifest.lock", "PODS:");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("dart")
+        .arg("--verbose")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Dart/Flutter"))
+        .stdout(predicate::str::contains("~/code/app/.dart_tool"))
+        .stdout(predicate::str::contains("~/code/app/ios/Pods"))
+        .stdout(predicate::str::contains("~/.pub-cache"))
+        .stdout(predicate::str::contains("~/flutter/bin/cache/artifacts"));
+}
+
+#[test]
+fn scan_dotnet_reports_nuget_locals_from_dotnet() {
+    let ctx = TestContext::new();
+    ctx.write_home_file("nuget/packages/newtonsoft.json/13.0.3/lib.dll", "dll");
+    ctx.write_home_file("code/Api/Api.csproj", "<Project/>");
+    ctx.write_home_file("code/Api/obj/project.assets.json", "{}");
+    ctx.create_mock_command(
+        "dotnet",
+        &format!(
+            "#!/bin/sh\necho \"global-packages: {}\"\n",
+            ctx.home().join("nuget/packages").display()
+        ),
+    );
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("dotnet")
+        .arg("--verbose")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(".NET"))
+        .stdout(predicate::str::contains("~/code/Api/obj"))
+        .stdout(predicate::str::contains("~/nuget/packages"));
+}
+
+#[test]
+fn scan_php_reports_composer_vendor_and_cache() {
+    let ctx = TestContext::new();
+    ctx.set_env("COMPOSER_CACHE_DIR", ctx.home().join("composer-cache"));
+    ctx.write_home_file("composer-cache/files/laravel/framework.zip", "zip");
+    ctx.write_home_file("code/shop/composer.json", "{}");
+    ctx.write_home_file("code/shop/vendor/autoload.php", "<?php");
+    ctx.write_home_file("code/notes/vendor/readme.txt", "not composer");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("php")
+        .arg("--verbose")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PHP"))
+        .stdout(predicate::str::contains("~/code/shop/vendor"))
+        .stdout(predicate::str::contains("~/composer-cache"))
+        .stdout(predicate::str::contains("notes/vendor").not());
+}
+
+#[test]
+fn scan_nodejs_reports_package_manager_caches_outside_current_mode() {
+    let ctx = TestContext::new();
+    ctx.set_env("npm_config_cache", ctx.home().join("npm"));
+    ctx.set_env("YARN_CACHE_FOLDER", ctx.home().join("yarn-cache"));
+    ctx.set_env("DENO_DIR", ctx.home().join("deno"));
+    ctx.write_home_file("npm/_cacache/index-v5/00/entry", "index");
+    ctx.write_home_file("yarn-cache/v6/npm-left-pad-1.3.0/package.json", "{}");
+    ctx.write_home_file("deno/deps/https/deno.land/mod.ts", "export {}");
+    ctx.write_home_file("code/web/node_modules/left-pad/index.js", "module.exports = 1");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--verbose")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("~/code/web/node_modules"))
+        .stdout(predicate::str::contains("~/npm/_cacache"))
+        .stdout(predicate::str::contains("~/yarn-cache"))
+        .stdout(predicate::str::contains("~/deno"));
+
+    ctx.cli_in(ctx.home().join("code/web"))
+        .arg("scan")
+        .arg("--type")
+        .arg("nodejs")
+        .arg("--current")
+        .arg("--verbose")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("node_modules"))
+        .stdout(predicate::str::contains("_cacache").not());
+}
+
+#[test]
+fn scan_rust_reports_cargo_home_caches_but_keeps_git_db() {
+    let ctx = TestContext::new();
+    ctx.set_env("CARGO_HOME", ctx.home().join("cargo"));
+    ctx.write_home_file(
+        "cargo/registry/cache/index.crates.io-6f17d22bba15001f/serde-1.0.0.crate",
+        "c",
+    );
+    ctx.write_home_file(
+        "cargo/registry/src/index.crates.io-6f17d22bba15001f/serde-1.0.0/lib.rs",
+        "",
+    );
+    ctx.write_home_file("cargo/git/checkouts/dep-1a2b/abc123/Cargo.toml", "[package]");
+    ctx.write_home_file("cargo/git/db/dep-1a2b/HEAD", "ref: refs/heads/main");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("rust")
+        .arg("--verbose")
+        .arg(ctx.work_dir())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("~/cargo/registry/cache"))
+        .stdout(predicate::str::contains("~/cargo/registry/src"))
+        .stdout(predicate::str::contains("~/cargo/git/checkouts"))
+        .stdout(predicate::str::contains("git/db").not());
+}
+
+#[test]
+fn scan_list_reports_rustup_toolchains_without_project_pins() {
+    let ctx = TestContext::new();
+    ctx.set_env("RUSTUP_HOME", ctx.home().join("rustup"));
+    ctx.create_home_dir("rustup/toolchains/1.75.0-x86_64-unknown-linux-gnu/bin");
+    ctx.create_home_dir("rustup/toolchains/1.90.0-x86_64-unknown-linux-gnu/bin");
+    ctx.write_home_file("code/tool/rust-toolchain", "1.90.0\n");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--list")
+        .arg("--type")
+        .arg("rustup")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1.75.0-x86_64-unknown-linux-gnu (not pinned)"))
+        .stdout(predicate::str::contains("1.90.0-x86_64").not());
+}
+
+#[test]
+fn scan_runtimes_reports_versions_without_project_references() {
+    let ctx = TestContext::new();
+    for version in ["v16.20.2", "v20.11.0", "v22.2.0"] {
+        ctx.write_home_file(format!(".nvm/versions/node/{version}/bin/node"), "node");
+    }
+    for version in ["3.11.9", "3.12.1"] {
+        ctx.write_home_file(
+            format!(".local/share/mise/installs/python/{version}/bin/python"),
+            "py",
+        );
+    }
+    ctx.write_home_file("code/web/.nvmrc", "20\n");
+    ctx.write_home_file("code/api/mise.toml", "[tools]\npython = \"3.11\"\n");
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--type")
+        .arg("runtimes")
+        .arg("--verbose")
+        .arg(ctx.home().join("code"))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("v16.20.2"))
+        .stdout(predicate::str::contains("v20.11.0").not())
+        .stdout(predicate::str::contains("v22.2.0").not())
+        .stdout(predicate::str::contains("3.11.9").not());
+
+    ctx.cli()
+        .arg("scan")
+        .arg("--current")
+        .arg("--type")
+        .arg("runtimes")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not supported with --current"));
+}
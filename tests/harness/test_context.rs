@@ -6,6 +6,7 @@ use std::fs;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 use tempfile::TempDir;
 
 pub(crate) struct TestContext {
@@ -50,6 +51,31 @@ impl TestContext {
         let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("prf"));
         cmd.current_dir(dir.as_ref()).env("HOME", &self.home);
 
+        // Keep prf config, state and trash inside the temporary home unless a test opts in.
+        for key in
+            ["PRF_CONFIG", "PRF_STATE_DIR", "XDG_CONFIG_HOME", "XDG_STATE_HOME", "XDG_DATA_HOME"]
+        {
+            cmd.env_remove(key);
+        }
+
+        // Toolchains and version managers export their homes (Cargo sets CARGO_HOME for tests);
+        // global scans must stay inside the fake home.
+        for key in [
+            "CARGO_HOME",
+            "RUSTUP_HOME",
+            "RUSTUP_TOOLCHAIN",
+            "SCCACHE_DIR",
+            "XDG_CACHE_HOME",
+            "NVM_DIR",
+            "PYENV_ROOT",
+            "RBENV_ROOT",
+            "ASDF_DATA_DIR",
+            "MISE_DATA_DIR",
+            "MISE_CONFIG_DIR",
+        ] {
+            cmd.env_remove(key);
+        }
+
         for (key, value) in self.env_vars.borrow().iter() {
             cmd.env(key, value);
         }
@@ -72,6 +98,17 @@ impl TestContext {
         path
     }
 
+    /// Backdates the modification time of a home path and everything below it.
+    pub(crate) fn age_home_path<P: AsRef<Path>>(&self, relative: P, age: Duration) {
+        let mtime = SystemTime::now() - age;
+        for entry in walkdir::WalkDir::new(self.home.join(relative.as_ref())).contents_first(true) {
+            let entry = entry.expect("aged path is readable");
+            fs::File::open(entry.path())
+                .and_then(|file| file.set_modified(mtime))
+                .expect("modification time is set");
+        }
+    }
+
     pub(crate) fn create_mock_command(&self, name: &str, script: &str) -> PathBuf {
         let path = self.bin_dir.join(name);
         fs::write(&path, script).expect("mock command is written");